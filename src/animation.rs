@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use bevy::{
+    prelude::*,
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    utils::HashMap,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::ingame::IngameState;
+
+const LAYOUT_LABEL: &str = "layout";
+
+// One animation clip of a sprite sheet.
+// `durations` is given per frame; the last value is reused for any remaining
+// frames, and an empty list makes the clip a still image.
+#[derive(Deserialize, Debug, Clone)]
+struct Animation {
+    frames: Vec<usize>,
+    #[serde(default)]
+    durations: Vec<f32>,
+    #[serde(default)]
+    looping: bool,
+    #[serde(default)]
+    next: Option<String>,
+}
+
+impl Animation {
+    fn duration(&self, frame: usize) -> Option<f32> {
+        self.durations.get(frame).or(self.durations.last()).copied()
+    }
+}
+
+#[derive(Deserialize)]
+struct AnimationSheetFile {
+    image_size: u32,
+    columns: u32,
+    rows: u32,
+    animations: HashMap<String, Animation>,
+}
+
+#[derive(Asset, TypePath, Debug)]
+pub struct AnimationSheet {
+    animations: HashMap<String, Animation>,
+}
+
+#[derive(Default)]
+struct AnimationSheetLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+enum AnimationSheetLoaderError {
+    #[error("Could not load asset: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for AnimationSheetLoader {
+    type Asset = AnimationSheet;
+    type Settings = ();
+    type Error = AnimationSheetLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file = ron::de::from_bytes::<AnimationSheetFile>(&bytes)?;
+        let layout = TextureAtlasLayout::from_grid(
+            UVec2::splat(file.image_size),
+            file.columns,
+            file.rows,
+            None,
+            None,
+        );
+        load_context.add_labeled_asset(LAYOUT_LABEL.to_string(), layout);
+
+        Ok(AnimationSheet { animations: file.animations })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["anim.ron"]
+    }
+}
+
+#[derive(Component)]
+pub struct AnimationPlayer {
+    sheet: Handle<AnimationSheet>,
+    current: String,
+    frame: usize,
+    timer: Timer,
+}
+
+impl AnimationPlayer {
+    pub fn new(sheet: Handle<AnimationSheet>, animation: &str) -> Self {
+        Self {
+            sheet,
+            current: animation.to_string(),
+            frame: 0,
+            timer: Timer::default(),
+        }
+    }
+
+    pub fn play(&mut self, animation: &str) {
+        self.current = animation.to_string();
+        self.frame = 0;
+        self.timer.reset();
+    }
+}
+
+// Texture atlas that uses the layout defined in the animation sheet at `path`
+pub fn texture_atlas(asset_server: &AssetServer, path: &str) -> TextureAtlas {
+    TextureAtlas {
+        layout: asset_server.load(format!("{}#{}", path, LAYOUT_LABEL)),
+        index: 0,
+    }
+}
+
+fn tick(
+    mut query: Query<&mut AnimationPlayer>,
+    sheets: Res<Assets<AnimationSheet>>,
+    time: Res<Time>,
+) {
+    for mut player in query.iter_mut() {
+        let Some(sheet) = sheets.get(&player.sheet) else { continue };
+        let Some(animation) = sheet.animations.get(&player.current) else { continue };
+        let Some(secs) = animation.duration(player.frame) else { continue };
+
+        player.timer.set_duration(Duration::from_secs_f32(secs));
+        player.timer.tick(time.delta());
+        if !player.timer.just_finished() { continue }
+
+        player.timer.reset();
+        if player.frame + 1 < animation.frames.len() {
+            player.frame += 1;
+        } else if animation.looping {
+            player.frame = 0;
+        } else if let Some(next) = &animation.next {
+            let next = next.clone();
+            player.play(&next);
+        }
+    }
+}
+
+fn apply_frame(
+    mut query: Query<(&AnimationPlayer, &mut TextureAtlas)>,
+    sheets: Res<Assets<AnimationSheet>>,
+) {
+    for (player, mut atlas) in query.iter_mut() {
+        let Some(sheet) = sheets.get(&player.sheet) else { continue };
+        let Some(animation) = sheet.animations.get(&player.current) else { continue };
+        let Some(&index) = animation.frames.get(player.frame) else { continue };
+
+        if atlas.index != index { atlas.index = index }
+    }
+}
+
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<AnimationSheet>()
+            .init_asset_loader::<AnimationSheetLoader>()
+            .add_systems(Update, (
+                tick.run_if(not(in_state(IngameState::Paused))),
+                apply_frame,
+            ).chain())
+        ;
+    }
+}
@@ -0,0 +1,117 @@
+use bevy::{
+    prelude::*,
+    audio::Volume,
+    ecs::system::SystemParam,
+    window::PrimaryWindow,
+};
+
+use crate::AppState;
+use crate::input::{
+    Action,
+    ActionState,
+};
+use crate::settings::Settings;
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AudioBus {
+    Sfx,
+    // no background music is shipped yet
+    #[allow(dead_code)]
+    Music,
+}
+
+impl AudioBus {
+    fn volume(&self, settings: &Settings, unfocused: &Unfocused) -> f32 {
+        if settings.muted || **unfocused { return 0.0 }
+
+        let bus_volume = match self {
+            AudioBus::Sfx => settings.sfx_volume,
+            AudioBus::Music => settings.music_volume,
+        };
+        settings.master_volume * bus_volume
+    }
+}
+
+// Whether a round is silenced because the window lost focus while `Settings::auto_pause` is on
+#[derive(Resource, Deref, Default)]
+struct Unfocused(bool);
+
+// Spawns sounds with the volume of their bus applied
+#[derive(SystemParam)]
+pub struct SoundPlayer<'w, 's> {
+    commands: Commands<'w, 's>,
+    settings: Res<'w, Settings>,
+    unfocused: Res<'w, Unfocused>,
+}
+
+impl SoundPlayer<'_, '_> {
+    pub fn play(&mut self, source: Handle<AudioSource>, bus: AudioBus) {
+        self.play_at_speed(source, bus, 1.0);
+    }
+
+    // a faster sound is also higher, so one sample can make more than one cue
+    pub fn play_at_speed(&mut self, source: Handle<AudioSource>, bus: AudioBus, speed: f32) {
+        let volume = Volume::new(bus.volume(&self.settings, &self.unfocused));
+
+        self.commands.spawn((
+            AudioBundle {
+                source,
+                settings: PlaybackSettings::DESPAWN.with_volume(volume).with_speed(speed),
+            },
+            bus,
+        ));
+    }
+}
+
+fn toggle_mute(
+    mut settings: ResMut<Settings>,
+    action_state: Res<ActionState>,
+) {
+    if !action_state.just_pressed(Action::Mute) { return }
+
+    settings.muted = !settings.muted;
+    println!("audio: muted {}", settings.muted);
+}
+
+fn focus(
+    mut unfocused: ResMut<Unfocused>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<Settings>,
+    app_state: Res<State<AppState>>,
+) {
+    let Ok(window) = window_query.get_single() else { return };
+
+    // only the round goes on behind the player's back, the menus just wait
+    let silenced = !window.focused && settings.auto_pause && *app_state.get() == AppState::Ingame;
+    if unfocused.0 == silenced { return }
+
+    unfocused.0 = silenced;
+    println!("audio: unfocused {}", unfocused.0);
+}
+
+fn apply_volume(
+    query: Query<(&AudioSink, &AudioBus)>,
+    settings: Res<Settings>,
+    unfocused: Res<Unfocused>,
+) {
+    if !settings.is_changed() && !unfocused.is_changed() { return }
+
+    for (sink, bus) in query.iter() {
+        sink.set_volume(bus.volume(&settings, &unfocused));
+    }
+}
+
+pub struct AudioBusPlugin;
+
+impl Plugin for AudioBusPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Unfocused>()
+            .add_systems(Update, (
+                toggle_mute,
+                focus,
+                apply_volume,
+            ).chain())
+        ;
+    }
+}
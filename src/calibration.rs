@@ -0,0 +1,260 @@
+use bevy::prelude::*;
+
+use crate::{
+    PATH_SOUND_REVERSAL,
+    MenuState,
+};
+use crate::audio::{
+    AudioBus,
+    SoundPlayer,
+};
+use crate::input::{
+    Action,
+    ActionState,
+};
+use crate::locale::{
+    HintKeys,
+    KeyHints,
+    Locale,
+    LocalizedText,
+};
+use crate::menu::overlay_bundle;
+use crate::settings::{
+    JUDGMENT_OFFSET_LIMIT,
+    Settings,
+};
+
+const CALIBRATION_SIZE: f32 = 24.0;
+const BACKGROUND_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const TEXT_SIZE: f32 = 16.0;
+const TEXT_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+const TEXT_PADDING: Val = Val::Px(8.0);
+const BEAT_SIZE: f32 = 48.0;
+const BEAT_COLOR: Color = Color::srgb(0.8, 0.2, 0.2);
+const BEAT_IDLE_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+const BEAT_FLASH_SECS: f32 = 0.1;
+const BEAT_INTERVAL: f32 = 0.6;
+const TAP_COUNT: usize = 8;
+
+#[derive(Resource, Deref)]
+struct MetronomeSound(Handle<AudioSource>);
+
+#[derive(Resource)]
+struct Metronome {
+    timer: Timer,
+    last_beat: f64,
+    offsets: Vec<f32>,
+}
+
+impl Default for Metronome {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(BEAT_INTERVAL, TimerMode::Repeating),
+            last_beat: f64::MIN,
+            offsets: Vec::new(),
+        }
+    }
+}
+
+impl Metronome {
+    // offset from the beat closest to `time`, negative when early
+    fn offset(&self, time: f64) -> f32 {
+        let late = time - self.last_beat;
+        let early = late - BEAT_INTERVAL as f64;
+        if late.abs() < early.abs() { late as f32 } else { early as f32 }
+    }
+
+    fn judgment_offset(&self) -> f32 {
+        let average = self.offsets.iter().sum::<f32>() / self.offsets.len() as f32;
+        average.clamp(-JUDGMENT_OFFSET_LIMIT, JUDGMENT_OFFSET_LIMIT)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.offsets.len() >= TAP_COUNT
+    }
+}
+
+#[derive(Component)]
+struct Calibration;
+
+#[derive(Component)]
+struct BeatIndicator;
+
+#[derive(Component)]
+struct CalibrationText;
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    println!("calibration: setup");
+    commands.insert_resource(MetronomeSound(asset_server.load(PATH_SOUND_REVERSAL)));
+    commands.insert_resource(Metronome::default());
+
+    let text_style = TextStyle {
+        font_size: TEXT_SIZE,
+        color: TEXT_COLOR,
+        ..Default::default()
+    };
+    let margin = Style {
+        margin: UiRect::all(TEXT_PADDING),
+        ..Default::default()
+    };
+
+    commands.spawn((
+        overlay_bundle(BACKGROUND_COLOR),
+        Calibration,
+        StateScoped(MenuState::Calibration),
+    ))
+    .insert(Name::new("calibration"))
+    .with_children(|parent| {
+        // title
+        parent.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: CALIBRATION_SIZE,
+                    color: TEXT_COLOR,
+                    ..Default::default()
+                },
+            )
+            .with_style(margin.clone()),
+            LocalizedText("calibration.title"),
+        ))
+        .insert(Name::new("title"));
+        // beat indicator
+        parent.spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Px(BEAT_SIZE),
+                    height: Val::Px(BEAT_SIZE),
+                    margin: UiRect::all(TEXT_PADDING),
+                    ..Default::default()
+                },
+                background_color: BEAT_IDLE_COLOR.into(),
+                ..Default::default()
+            },
+            BeatIndicator,
+        ))
+        .insert(Name::new("beat"));
+        // help
+        parent.spawn((
+            TextBundle::from_section("", text_style.clone())
+                .with_style(margin.clone()),
+            LocalizedText("calibration.help"),
+        ))
+        .insert(Name::new("help"));
+        // taps and result
+        parent.spawn((
+            TextBundle::from_sections([
+                TextSection::from_style(text_style.clone()),
+                TextSection::from_style(text_style.clone()),
+            ])
+            .with_style(margin.clone()),
+            CalibrationText,
+        ))
+        .insert(Name::new("result"));
+        // back
+        parent.spawn((
+            TextBundle::from_section("", text_style.clone())
+                .with_style(margin),
+            KeyHints(&[
+                ("calibration.retry", HintKeys::Action(Action::Retry)),
+                ("common.back", HintKeys::Action(Action::Back)),
+            ]),
+        ))
+        .insert(Name::new("back"));
+    });
+}
+
+fn metronome(
+    mut metronome: ResMut<Metronome>,
+    mut sound_player: SoundPlayer,
+    mut query: Query<&mut BackgroundColor, With<BeatIndicator>>,
+    sound: Res<MetronomeSound>,
+    time: Res<Time>,
+) {
+    metronome.timer.tick(time.delta());
+    if metronome.timer.just_finished() {
+        // the beat happened when the timer wrapped, not at the start of this frame
+        metronome.last_beat = time.elapsed_seconds_f64() - metronome.timer.elapsed_secs() as f64;
+        sound_player.play(sound.clone(), AudioBus::Sfx);
+    }
+
+    let Ok(mut color) = query.get_single_mut() else { return };
+    let since_beat = time.elapsed_seconds_f64() - metronome.last_beat;
+    *color = if since_beat < BEAT_FLASH_SECS as f64 { BEAT_COLOR } else { BEAT_IDLE_COLOR }.into();
+}
+
+fn tap(
+    mut metronome: ResMut<Metronome>,
+    mut settings: ResMut<Settings>,
+    action_state: Res<ActionState>,
+    time: Res<Time>,
+) {
+    if metronome.is_finished() || metronome.last_beat == f64::MIN { return }
+    if !action_state.just_pressed(Action::Hit)
+    && !action_state.just_pressed(Action::Confirm) { return }
+
+    let offset = metronome.offset(time.elapsed_seconds_f64());
+    println!("calibration: tap offset {}", offset);
+    metronome.offsets.push(offset);
+
+    if metronome.is_finished() {
+        settings.judgment_offset = metronome.judgment_offset();
+        println!("calibration: judgment offset {}", settings.judgment_offset);
+    }
+}
+
+fn update_text(
+    mut query: Query<&mut Text, With<CalibrationText>>,
+    metronome: Res<Metronome>,
+    locale: Locale,
+) {
+    if !metronome.is_changed() && !locale.is_changed() { return }
+
+    let Ok(mut text) = query.get_single_mut() else { return };
+    text.sections[0].value = locale.get(
+        if metronome.is_finished() { "calibration.result" } else { "calibration.taps" }
+    );
+    text.sections[1].value = if metronome.is_finished() {
+        format!(
+            "{:+}ms{}",
+            (metronome.judgment_offset() * 1000.0).round(),
+            locale.get("calibration.saved"),
+        )
+    } else {
+        format!("{}/{}", metronome.offsets.len(), TAP_COUNT)
+    };
+}
+
+fn update(
+    mut metronome: ResMut<Metronome>,
+    mut next_state: ResMut<NextState<MenuState>>,
+    action_state: Res<ActionState>,
+) {
+    if action_state.just_pressed(Action::Retry) {
+        println!("calibration: retry");
+        *metronome = Metronome::default();
+    }
+    if action_state.just_pressed(Action::Back) {
+        println!("calibration: moved state to Options from Calibration");
+        next_state.set(MenuState::Options);
+    }
+}
+
+pub struct CalibrationPlugin;
+
+impl Plugin for CalibrationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(MenuState::Calibration), setup)
+            .add_systems(Update, (
+                metronome,
+                tap,
+                update_text,
+                update,
+            ).chain().run_if(in_state(MenuState::Calibration)))
+        ;
+    }
+}
@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+
+use crate::{
+    MenuState,
+};
+use crate::input::{
+    Action,
+    ActionState,
+};
+use crate::locale::{
+    HintKeys,
+    KeyHints,
+    Locale,
+    LocalizedText,
+};
+use crate::menu::overlay_bundle;
+
+const CREDITS_SIZE: f32 = 24.0;
+// roles are translated, names are kept as their owners write them
+const CREDITS: [(&str, &str); 10] = [
+    ("credits.developer", "ittokunvim"),
+    ("credits.engine", "Bevy"),
+    ("credits.tileset", "SunnyLand"),
+    ("credits.font", "美咲フォント"),
+    ("credits.timingbutton", "いらすとや"),
+    ("credits.pausebutton", "ICOOON MONO"),
+    ("credits.sounds", "効果音ラボ"),
+    ("credits.voice", "ゆくも！"),
+    ("credits.image_editing", "Pixlr"),
+    ("credits.wasm", "wasm-bindgen"),
+];
+const BACKGROUND_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const TEXT_SIZE: f32 = 12.0;
+const TEXT_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+const TEXT_PADDING: Val = Val::Px(4.0);
+
+#[derive(Component)]
+struct Credits;
+
+#[derive(Component)]
+struct CreditText(usize);
+
+fn setup(mut commands: Commands) {
+    println!("credits: setup");
+    let text_style = TextStyle {
+        font_size: TEXT_SIZE,
+        color: TEXT_COLOR,
+        ..Default::default()
+    };
+    let margin = Style {
+        margin: UiRect::all(TEXT_PADDING),
+        ..Default::default()
+    };
+
+    commands.spawn((
+        overlay_bundle(BACKGROUND_COLOR),
+        Credits,
+        StateScoped(MenuState::Credits),
+    ))
+    .insert(Name::new("credits"))
+    .with_children(|parent| {
+        // title
+        parent.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: CREDITS_SIZE,
+                    color: TEXT_COLOR,
+                    ..Default::default()
+                },
+            )
+            .with_style(margin.clone()),
+            LocalizedText("credits.title"),
+        ))
+        .insert(Name::new("title"));
+        // credits
+        for (index, (_, name)) in CREDITS.into_iter().enumerate() {
+            parent.spawn((
+                TextBundle::from_section("", text_style.clone())
+                    .with_style(margin.clone()),
+                CreditText(index),
+            ))
+            .insert(Name::new(name));
+        }
+        // back
+        parent.spawn((
+            TextBundle::from_section("", text_style.clone())
+                .with_style(margin),
+            KeyHints(&[("common.back", HintKeys::Action(Action::Back))]),
+        ))
+        .insert(Name::new("back"));
+    });
+}
+
+fn update_text(
+    mut query: Query<(&mut Text, Ref<CreditText>)>,
+    locale: Locale,
+) {
+    for (mut text, credit) in query.iter_mut() {
+        if !locale.is_changed() && !credit.is_added() { continue }
+
+        let (role, name) = CREDITS[credit.0];
+        text.sections[0].value = format!("{}: {}", locale.get(role), name);
+    }
+}
+
+fn update(
+    mut next_state: ResMut<NextState<MenuState>>,
+    action_state: Res<ActionState>,
+) {
+    // any click also closes the screen, so it works without a keyboard
+    if !action_state.just_pressed(Action::Back)
+    && !action_state.just_pressed(Action::Confirm) { return }
+
+    println!("credits: moved state to Closed from Credits");
+    next_state.set(MenuState::Closed);
+}
+
+pub struct CreditsPlugin;
+
+impl Plugin for CreditsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(MenuState::Credits), setup)
+            .add_systems(Update, (
+                update_text,
+                update,
+            ).run_if(in_state(MenuState::Credits)))
+        ;
+    }
+}
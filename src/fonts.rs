@@ -0,0 +1,76 @@
+use ab_glyph::Font as _;
+use bevy::{
+    prelude::*,
+    ui::UiSystem,
+};
+
+use crate::PATH_FONT;
+
+// Misaki Gothic draws both languages, then the built-in font of Bevy, so some glyph is always drawn
+#[derive(Resource)]
+pub struct FontRegistry {
+    fonts: [Handle<Font>; 2],
+}
+
+impl FromWorld for FontRegistry {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let fonts = [asset_server.load(PATH_FONT), Handle::default()];
+
+        Self { fonts }
+    }
+}
+
+impl FontRegistry {
+    // the first font that can draw all of `text`, or the first one while they load
+    fn choose(&self, text: &str, fonts: &Assets<Font>) -> Handle<Font> {
+        self.fonts.iter()
+            .find(|handle| fonts.get(*handle).is_some_and(|font| covers(font, text)))
+            .unwrap_or(&self.fonts[0])
+            .clone()
+    }
+}
+
+fn covers(font: &Font, text: &str) -> bool {
+    text.chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|c| font.font.glyph_id(c).0 != 0)
+}
+
+fn apply(
+    mut query: Query<&mut Text>,
+    mut events: EventReader<AssetEvent<Font>>,
+    registry: Res<FontRegistry>,
+    fonts: Res<Assets<Font>>,
+) {
+    // a font that has just loaded may draw what the previous choice could not
+    let loaded = events.read().count() > 0;
+
+    for mut text in query.iter_mut() {
+        if !loaded && !text.is_changed() { continue }
+
+        let chosen: Vec<_> = text.sections.iter()
+            .map(|section| registry.choose(&section.value, &fonts))
+            .collect();
+        // writing only real changes keeps this from running again on its own change
+        if text.sections.iter().zip(&chosen).all(|(section, font)| section.style.font == *font) {
+            continue;
+        }
+
+        for (section, font) in text.sections.iter_mut().zip(chosen) {
+            section.style.font = font;
+        }
+    }
+}
+
+pub struct FontsPlugin;
+
+impl Plugin for FontsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<FontRegistry>()
+            // after every system of the frame wrote its text, before the text is measured
+            .add_systems(PostUpdate, apply.before(UiSystem::Layout))
+        ;
+    }
+}
@@ -0,0 +1,42 @@
+use bevy::{
+    prelude::*,
+    input::gamepad::{
+        GamepadConnection,
+        GamepadConnectionEvent,
+    },
+};
+
+use crate::ingame::IngameState;
+
+fn connection(
+    mut events: EventReader<GamepadConnectionEvent>,
+    mut next_state: ResMut<NextState<IngameState>>,
+    state: Option<Res<State<IngameState>>>,
+) {
+    for event in events.read() {
+        match &event.connection {
+            GamepadConnection::Connected(info) => {
+                println!("gamepad: connected {:?} {}", event.gamepad, info.name);
+            }
+            GamepadConnection::Disconnected => {
+                println!("gamepad: disconnected {:?}", event.gamepad);
+                // only a round in progress is paused, and it does not exist outside of Ingame
+                let Some(state) = state.as_ref() else { continue };
+                if *state.get() != IngameState::Paused {
+                    println!("gamepad: moved state to Paused from {:?}", state.get());
+                    next_state.set(IngameState::Paused);
+                }
+            }
+        }
+    }
+}
+
+pub struct GamepadPlugin;
+
+impl Plugin for GamepadPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, connection)
+        ;
+    }
+}
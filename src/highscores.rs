@@ -0,0 +1,178 @@
+use bevy::{
+    prelude::*,
+    utils::HashMap,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    PATH_HIGHSCORES,
+    AppState,
+    MenuState,
+    Score,
+};
+use crate::input::{
+    Action,
+    ActionState,
+};
+use crate::locale::{
+    HintKeys,
+    KeyHints,
+    Locale,
+    LocalizedText,
+};
+use crate::menu::overlay_bundle;
+use crate::settings::{
+    DIFFICULTIES,
+    Difficulty,
+    Settings,
+};
+use crate::storage::{
+    load_ron,
+    save_ron,
+};
+
+const HIGHSCORES_SIZE: f32 = 24.0;
+const NO_SCORE_TEXT: &str = "---";
+const BACKGROUND_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const TEXT_SIZE: f32 = 16.0;
+const TEXT_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+const TEXT_PADDING: Val = Val::Px(8.0);
+
+// Best score of each difficulty
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+pub struct HighScores(HashMap<Difficulty, usize>);
+
+impl HighScores {
+    pub fn get(&self, difficulty: Difficulty) -> Option<usize> {
+        self.0.get(&difficulty).copied()
+    }
+
+    // returns true when the score is a new record
+    pub fn record(&mut self, difficulty: Difficulty, score: usize) -> bool {
+        if self.get(difficulty).is_some_and(|best| best >= score) { return false }
+
+        self.0.insert(difficulty, score);
+        true
+    }
+
+    pub fn load() -> Self {
+        load_ron(PATH_HIGHSCORES)
+    }
+
+    pub fn save(&self) {
+        save_ron(PATH_HIGHSCORES, self);
+    }
+}
+
+#[derive(Component)]
+struct HighScoresUi;
+
+#[derive(Component)]
+struct HighScoreText(Difficulty);
+
+fn record(
+    mut high_scores: ResMut<HighScores>,
+    score: Res<Score>,
+    settings: Res<Settings>,
+) {
+    if !high_scores.record(settings.difficulty, **score) { return }
+
+    println!("highscores: new record {} on {:?}", **score, settings.difficulty);
+    high_scores.save();
+}
+
+fn setup(mut commands: Commands) {
+    println!("highscores: setup");
+    let text_style = TextStyle {
+        font_size: TEXT_SIZE,
+        color: TEXT_COLOR,
+        ..Default::default()
+    };
+    let margin = Style {
+        margin: UiRect::all(TEXT_PADDING),
+        ..Default::default()
+    };
+
+    commands.spawn((
+        overlay_bundle(BACKGROUND_COLOR),
+        HighScoresUi,
+        StateScoped(MenuState::HighScores),
+    ))
+    .insert(Name::new("highscores"))
+    .with_children(|parent| {
+        // title
+        parent.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: HIGHSCORES_SIZE,
+                    color: TEXT_COLOR,
+                    ..Default::default()
+                },
+            )
+            .with_style(margin.clone()),
+            LocalizedText("highscores.title"),
+        ))
+        .insert(Name::new("title"));
+        // scores
+        for difficulty in DIFFICULTIES {
+            parent.spawn((
+                TextBundle::from_section("", text_style.clone())
+                    .with_style(margin.clone()),
+                HighScoreText(difficulty),
+            ))
+            .insert(Name::new(format!("{:?}", difficulty)));
+        }
+        // back
+        parent.spawn((
+            TextBundle::from_section("", text_style.clone())
+                .with_style(margin),
+            KeyHints(&[("common.back", HintKeys::Action(Action::Back))]),
+        ))
+        .insert(Name::new("back"));
+    });
+}
+
+fn update_text(
+    mut query: Query<(&mut Text, Ref<HighScoreText>)>,
+    high_scores: Res<HighScores>,
+    locale: Locale,
+) {
+    for (mut text, score_text) in query.iter_mut() {
+        if !locale.is_changed() && !score_text.is_added() { continue }
+
+        let difficulty = score_text.0;
+        let score = high_scores.get(difficulty)
+            .map_or(NO_SCORE_TEXT.to_string(), |score| score.to_string());
+        text.sections[0].value = format!("{}: {}", locale.get(difficulty.text_key()), score);
+    }
+}
+
+fn update(
+    mut next_state: ResMut<NextState<MenuState>>,
+    action_state: Res<ActionState>,
+) {
+    // any click also closes the screen, so it works without a keyboard
+    if !action_state.just_pressed(Action::Back)
+    && !action_state.just_pressed(Action::Confirm) { return }
+
+    println!("highscores: moved state to Closed from HighScores");
+    next_state.set(MenuState::Closed);
+}
+
+pub struct HighScoresPlugin;
+
+impl Plugin for HighScoresPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(HighScores::load())
+            .add_systems(OnEnter(AppState::Gameover), record)
+            .add_systems(OnEnter(AppState::Gameclear), record)
+            .add_systems(OnEnter(MenuState::HighScores), setup)
+            .add_systems(Update, (
+                update_text,
+                update,
+            ).run_if(in_state(MenuState::HighScores)))
+        ;
+    }
+}
@@ -3,7 +3,6 @@ use bevy::prelude::*;
 use crate::{
     PATH_IMAGE_BAR,
     AppState,
-    Config,
 };
 use crate::ingame::GRID_SIZE;
 
@@ -15,10 +14,7 @@ pub struct Bar;
 fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    config: Res<Config>,
 ) {
-    if !config.setup_ingame { return }
-
     println!("bar: setup");
     let (x, y, z) = (
         0.0,
@@ -37,25 +33,17 @@ fn setup(
             ..Default::default()
         },
         Bar,
+        StateScoped(AppState::Ingame),
     ))
     .insert(Name::new("bar"));
 }
 
-fn despawn(
-    mut commands: Commands,
-    query: Query<Entity, With<Bar>>,
-) {
-    println!("bar: despawn");
-    for entity in query.iter() { commands.entity(entity).despawn() }
-}
-
 pub struct BarPlugin;
 
 impl Plugin for BarPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_systems(OnEnter(AppState::Ingame), setup)
-            .add_systems(OnEnter(AppState::Mainmenu), despawn)
         ;
     }
 }
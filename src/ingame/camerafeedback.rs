@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::settings::Settings;
+use crate::viewport::MainCamera;
+use crate::ingame::{
+    PerfectEvent,
+    GoodEvent,
+    BadEvent,
+    IngameState,
+};
+
+// trauma added by a miss, out of 1.0 for the strongest shake
+const BAD_TRAUMA: f32 = 0.6;
+// trauma lost per second
+const TRAUMA_DECAY: f32 = 1.5;
+const SHAKE_OFFSET: f32 = 12.0;
+const SHAKE_ANGLE: f32 = 0.05;
+// radians per second of the waves making up the shake, x, y and rotation
+const SHAKE_FREQUENCIES: Vec3 = Vec3::new(37.0, 41.0, 29.0);
+const HIT_STOP: Duration = Duration::from_millis(80);
+const PERFECT_PULSE: f32 = 1.0;
+const GOOD_PULSE: f32 = 0.5;
+// pulse lost per second
+const PULSE_DECAY: f32 = 4.0;
+// how much closer the view gets at a full pulse
+const PULSE_ZOOM: f32 = 0.04;
+
+#[derive(Resource, Default)]
+struct CameraFeedback {
+    // the shake grows with its square, so a fading shake settles smoothly
+    trauma: f32,
+    pulse: f32,
+    // seconds the feedback has run, to move along the waves
+    phase: f32,
+    // real time when the frozen world moves again
+    hit_stop_until: Option<Duration>,
+}
+
+fn react(
+    mut feedback: ResMut<CameraFeedback>,
+    mut perfect_events: EventReader<PerfectEvent>,
+    mut good_events: EventReader<GoodEvent>,
+    mut bad_events: EventReader<BadEvent>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
+    settings: Res<Settings>,
+) {
+    let perfect = perfect_events.read().count() > 0;
+    let good = good_events.read().count() > 0;
+    let bad = bad_events.read().count() > 0;
+
+    if bad && settings.screen_shake {
+        feedback.trauma = (feedback.trauma + BAD_TRAUMA).min(1.0);
+    }
+    if settings.zoom_pulse {
+        if perfect {
+            feedback.pulse = PERFECT_PULSE;
+        } else if good {
+            feedback.pulse = feedback.pulse.max(GOOD_PULSE);
+        }
+    }
+    if perfect && settings.hit_stop {
+        println!("camerafeedback: hit stop");
+        feedback.hit_stop_until = Some(real_time.elapsed() + HIT_STOP);
+        virtual_time.pause();
+    }
+}
+
+// Waits on real time, as the clock of `Time` is the one that is stopped
+fn release_hit_stop(
+    mut feedback: ResMut<CameraFeedback>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
+) {
+    let Some(until) = feedback.hit_stop_until else { return };
+    if real_time.elapsed() < until { return }
+
+    feedback.hit_stop_until = None;
+    virtual_time.unpause();
+}
+
+fn apply(
+    mut feedback: ResMut<CameraFeedback>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+    real_time: Res<Time<Real>>,
+    settings: Res<Settings>,
+) {
+    // real time, so the shake and the pulse go on through a hit stop
+    let delta = real_time.delta_seconds();
+    feedback.trauma = (feedback.trauma - TRAUMA_DECAY * delta).max(0.0);
+    feedback.pulse = (feedback.pulse - PULSE_DECAY * delta).max(0.0);
+    feedback.phase += delta;
+
+    let Ok((mut transform, mut projection)) = camera_query.get_single_mut() else { return };
+
+    // turned off on the way, the camera settles at once
+    let shake = if settings.screen_shake { feedback.trauma.powi(2) } else { 0.0 };
+    let pulse = if settings.zoom_pulse { feedback.pulse } else { 0.0 };
+    let waves = SHAKE_FREQUENCIES * feedback.phase;
+    let offset = Vec2::new(waves.x.sin(), waves.y.sin()) * SHAKE_OFFSET * shake;
+    let rotation = Quat::from_rotation_z(waves.z.sin() * SHAKE_ANGLE * shake);
+    let scale = 1.0 - pulse * PULSE_ZOOM;
+
+    if transform.translation.truncate() != offset {
+        transform.translation.x = offset.x;
+        transform.translation.y = offset.y;
+    }
+    if transform.rotation != rotation { transform.rotation = rotation }
+    if projection.scale != scale { projection.scale = scale }
+}
+
+// The camera outlives the round, so it is put back whenever play stops
+fn reset(
+    mut feedback: ResMut<CameraFeedback>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    println!("camerafeedback: reset");
+    *feedback = CameraFeedback::default();
+    virtual_time.unpause();
+
+    let Ok((mut transform, mut projection)) = camera_query.get_single_mut() else { return };
+    transform.translation.x = 0.0;
+    transform.translation.y = 0.0;
+    transform.rotation = Quat::IDENTITY;
+    projection.scale = 1.0;
+}
+
+pub struct CameraFeedbackPlugin;
+
+impl Plugin for CameraFeedbackPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<CameraFeedback>()
+            .add_systems(Update, release_hit_stop)
+            .add_systems(Update, (
+                react,
+                apply,
+            ).chain().run_if(in_state(IngameState::Playing)))
+            // a pause or its countdown shows the round at rest
+            .add_systems(OnExit(IngameState::Playing), reset)
+            .add_systems(OnExit(AppState::Ingame), reset)
+        ;
+    }
+}
@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+
+use crate::PATH_SOUND_CLICK;
+use crate::audio::{
+    AudioBus,
+    SoundPlayer,
+};
+use crate::locale::LocalizedText;
+use crate::ingame::IngameState;
+
+const COUNTDOWN_SECS: u32 = 3;
+// the click an octave up, so the start stands out from the count
+const GO_SPEED: f32 = 2.0;
+const GO_SECS: f32 = 0.5;
+const TEXT_SIZE: f32 = 64.0;
+const TEXT_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+
+#[derive(Resource)]
+struct CountdownTimer {
+    timer: Timer,
+    // the number on screen, to sound a cue when it changes
+    count: u32,
+}
+
+#[derive(Resource)]
+struct CountdownSound(Handle<AudioSource>);
+
+#[derive(Component)]
+struct CountdownText;
+
+#[derive(Component, Deref, DerefMut)]
+struct GoText(Timer);
+
+// `text` is added to the text itself, next to its style
+fn spawn_text(
+    commands: &mut Commands,
+    value: String,
+    text: impl Bundle,
+) -> Entity {
+    commands.spawn(NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section(
+                value,
+                TextStyle {
+                    font_size: TEXT_SIZE,
+                    color: TEXT_COLOR,
+                    ..Default::default()
+                },
+            ),
+            text,
+        ));
+    })
+    .id()
+}
+
+fn setup(
+    mut commands: Commands,
+    mut sound_player: SoundPlayer,
+    asset_server: Res<AssetServer>,
+) {
+    println!("countdown: setup");
+    let sound = asset_server.load(PATH_SOUND_CLICK);
+    sound_player.play(sound.clone(), AudioBus::Sfx);
+    commands.insert_resource(CountdownSound(sound));
+    commands.insert_resource(CountdownTimer {
+        timer: Timer::from_seconds(COUNTDOWN_SECS as f32, TimerMode::Once),
+        count: COUNTDOWN_SECS,
+    });
+
+    let entity = spawn_text(&mut commands, COUNTDOWN_SECS.to_string(), CountdownText);
+    commands.entity(entity)
+        .insert((Name::new("countdown"), StateScoped(IngameState::Countdown)));
+}
+
+fn update(
+    mut countdown: ResMut<CountdownTimer>,
+    mut next_state: ResMut<NextState<IngameState>>,
+    mut sound_player: SoundPlayer,
+    mut query: Query<&mut Text, With<CountdownText>>,
+    sound: Res<CountdownSound>,
+    time: Res<Time>,
+) {
+    if countdown.timer.tick(time.delta()).just_finished() {
+        sound_player.play_at_speed(sound.0.clone(), AudioBus::Sfx, GO_SPEED);
+        println!("countdown: moved state to Playing from Countdown");
+        next_state.set(IngameState::Playing);
+        return;
+    }
+
+    let count = countdown.timer.remaining_secs().ceil() as u32;
+    if count == countdown.count { return }
+
+    countdown.count = count;
+    sound_player.play(sound.0.clone(), AudioBus::Sfx);
+    let Ok(mut text) = query.get_single_mut() else { return };
+    text.sections[0].value = count.to_string();
+}
+
+fn go(mut commands: Commands) {
+    let entity = spawn_text(&mut commands, String::new(), LocalizedText("countdown.go"));
+    commands.entity(entity).insert((
+        Name::new("go"),
+        GoText(Timer::from_seconds(GO_SECS, TimerMode::Once)),
+        StateScoped(IngameState::Playing),
+    ));
+}
+
+fn update_go(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut GoText)>,
+    time: Res<Time>,
+) {
+    for (entity, mut timer) in query.iter_mut() {
+        if timer.tick(time.delta()).just_finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub struct CountdownPlugin;
+
+impl Plugin for CountdownPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(IngameState::Countdown), setup)
+            .add_systems(Update, update.run_if(in_state(IngameState::Countdown)))
+            // the round only ever starts or resumes through the countdown
+            .add_systems(OnEnter(IngameState::Playing), go)
+            .add_systems(Update, update_go.run_if(in_state(IngameState::Playing)))
+        ;
+    }
+}
@@ -1,41 +1,68 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 
 use crate::{
     PATH_IMAGE_CUE,
     AppState,
-    Config,
 };
+use crate::settings::Settings;
 use crate::ingame::{
     GRID_SIZE,
+    Judgment,
     PerfectEvent,
     GoodEvent,
     OkEvent,
     BadEvent,
     TimingEvent,
     ReversalEvent,
+    IngameState,
 };
 use crate::ingame::bar::{
     SIZE as BAR_SIZE,
     Bar,
 };
 
-#[derive(Component)]
-pub struct Cue;
+#[derive(Component, Default)]
+pub struct Cue {
+    // when the transform was last moved, on the clock of `Time`
+    moved_at: Duration,
+}
 
 #[derive(Component, Deref, DerefMut)]
 struct Velocity(Vec2);
 
+// Hits that were judged, to hold back the ones that come too soon
+#[derive(Resource, Default)]
+struct HitLimiter {
+    last_hit: Option<Duration>,
+    hit_this_pass: bool,
+}
+
+impl HitLimiter {
+    fn accept(&mut self, timestamp: Duration, settings: &Settings) -> bool {
+        if settings.one_hit_per_pass && self.hit_this_pass { return false }
+        if let Some(last_hit) = self.last_hit {
+            let since = timestamp.saturating_sub(last_hit).as_secs_f32();
+            if since < settings.hit_cooldown { return false }
+        }
+        self.last_hit = Some(timestamp);
+        self.hit_this_pass = true;
+        true
+    }
+}
+
 const SIZE: f32 = 48.0;
 const SPEED: f32 = 400.0;
 
 fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    config: Res<Config>,
+    settings: Res<Settings>,
 ) {
-    if !config.setup_ingame { return }
-
     println!("cue: setup");
+    commands.insert_resource(HitLimiter::default());
+    let speed = SPEED * settings.difficulty.speed_scale();
     let (x, y, z): (f32, f32, f32) = (
         GRID_SIZE * 10.0,
         GRID_SIZE * 10.0,
@@ -51,18 +78,19 @@ fn setup(
             transform: Transform::from_xyz(x, y, z),
             ..Default::default()
         },
-        Cue,
-        Velocity(Vec2::new(-SPEED, 0.0)),
+        Cue::default(),
+        Velocity(Vec2::new(-speed, 0.0)),
+        StateScoped(AppState::Ingame),
     ));
 }
 
 fn apply_velocity(
-    mut cue_query: Query<(&mut Transform, &mut Velocity), (With<Cue>, Without<Bar>)>,
+    mut cue_query: Query<(&mut Cue, &mut Transform, &mut Velocity), Without<Bar>>,
     mut events: EventWriter<ReversalEvent>,
     bar_query: Query<&Transform, (With<Bar>, Without<Cue>)>,
-    time_step: Res<Time<Fixed>>,
+    time: Res<Time>,
 ) {
-    let Ok((mut cue_transform, mut cue_velocity)) =
+    let Ok((mut cue, mut cue_transform, mut cue_velocity)) =
         cue_query.get_single_mut() else { return };
     let bar_transform = bar_query.single();
     let cue_x = cue_transform.translation.x;
@@ -73,49 +101,82 @@ fn apply_velocity(
         // reversal velocity
         cue_velocity.x = -cue_velocity.x;
     }
-    // update cue x
-    cue_transform.translation.x += cue_velocity.x * time_step.delta().as_secs_f32();
+    // update cue x, by the real length of the frame so the rewind in `position_before` holds
+    cue_transform.translation.x += cue_velocity.x * time.delta_seconds();
+    cue.moved_at = time.elapsed();
+}
+
+// Where a cue now at `x` was `secs` seconds ago, bouncing between `min` and `max`
+fn position_before(x: f32, velocity: f32, secs: f32, min: f32, max: f32) -> f32 {
+    let width = max - min;
+    // unfold the bounces into a line that the cue always travels forward on
+    let unfolded = if velocity >= 0.0 { x - min } else { 2.0 * width - (x - min) };
+    let unfolded = (unfolded - velocity.abs() * secs).rem_euclid(2.0 * width);
+    min + if unfolded <= width { unfolded } else { 2.0 * width - unfolded }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn send_events(
     mut timing_events: EventReader<TimingEvent>,
+    mut reversal_events: EventReader<ReversalEvent>,
     mut perfect_events: EventWriter<PerfectEvent>,
     mut good_events: EventWriter<GoodEvent>,
     mut ok_events: EventWriter<OkEvent>,
     mut bad_events: EventWriter<BadEvent>,
-    cue_query: Query<&Transform, (With<Cue>, Without<Bar>)>,
+    mut limiter: ResMut<HitLimiter>,
+    cue_query: Query<(&Cue, &Transform, &Velocity), Without<Bar>>,
     bar_query: Query<&Transform, (With<Bar>, Without<Cue>)>,
+    settings: Res<Settings>,
 ) {
-    if timing_events.is_empty() { return }
-    timing_events.clear();
-
-    let cue_x = cue_query.single().translation.x;
+    let (cue, cue_transform, cue_velocity) = cue_query.single();
     let bar_x = bar_query.single().translation.x;
 
-    // perfect
-    if cue_x < bar_x + GRID_SIZE && cue_x > bar_x - GRID_SIZE {
-        perfect_events.send_default();
-    }
-    // good
-    else if cue_x < bar_x + (GRID_SIZE * 2.0) && cue_x > bar_x - (GRID_SIZE * 2.0) {
-        good_events.send_default();
-    }
-    // ok
-    else if cue_x < bar_x + (GRID_SIZE * 4.0) && cue_x > bar_x - (GRID_SIZE * 4.0) {
-        ok_events.send_default();
-    }
-    // bad
-    else {
-        bad_events.send_default();
+    // every input is judged on its own, in the order it arrived
+    for (index, event) in timing_events.read().enumerate() {
+        if !limiter.accept(event.timestamp, &settings) {
+            println!("cue: hit rejected");
+            continue;
+        }
+
+        // judge where the cue was in the frame that read the input, shifted by the player's offset
+        let secs = (cue.moved_at.as_secs_f64() - event.timestamp.as_secs_f64()) as f32
+            + settings.judgment_offset;
+        let cue_x = position_before(
+            cue_transform.translation.x,
+            cue_velocity.x,
+            secs,
+            bar_x - BAR_SIZE.x / 2.0,
+            bar_x + BAR_SIZE.x / 2.0,
+        );
+
+        let judgment = Judgment {
+            position: Vec2::new(cue_x, cue_transform.translation.y),
+            timestamp: event.timestamp,
+            index,
+        };
+
+        // perfect
+        if cue_x < bar_x + GRID_SIZE && cue_x > bar_x - GRID_SIZE {
+            perfect_events.send(PerfectEvent(judgment));
+        }
+        // good
+        else if cue_x < bar_x + (GRID_SIZE * 2.0) && cue_x > bar_x - (GRID_SIZE * 2.0) {
+            good_events.send(GoodEvent(judgment));
+        }
+        // ok
+        else if cue_x < bar_x + (GRID_SIZE * 4.0) && cue_x > bar_x - (GRID_SIZE * 4.0) {
+            ok_events.send(OkEvent(judgment));
+        }
+        // bad
+        else {
+            bad_events.send(BadEvent(judgment));
+        }
     }
-}
 
-fn despawn(
-    mut commands: Commands,
-    query: Query<Entity, With<Cue>>,
-) {
-    println!("cue: despawn");
-    for entity in query.iter() { commands.entity(entity).despawn() }
+    // inputs of this frame came before the bounce, so the next pass starts after them
+    if reversal_events.read().count() > 0 {
+        limiter.hit_this_pass = false;
+    }
 }
 
 pub struct CuePlugin;
@@ -127,8 +188,76 @@ impl Plugin for CuePlugin {
             .add_systems(Update, (
                 apply_velocity,
                 send_events,
-            ).run_if(in_state(AppState::Ingame)))
-            .add_systems(OnEnter(AppState::Mainmenu), despawn)
+            ).chain().run_if(in_state(IngameState::Playing)))
         ;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIN: f32 = -100.0;
+    const MAX: f32 = 100.0;
+
+    fn assert_near(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-3, "{} != {}", actual, expected);
+    }
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn hit_limiter_accepts_every_hit_by_default() {
+        let settings = Settings::default();
+        let mut limiter = HitLimiter::default();
+        assert!(limiter.accept(millis(1000), &settings));
+        assert!(limiter.accept(millis(1000), &settings));
+        assert!(limiter.accept(millis(1001), &settings));
+    }
+
+    #[test]
+    fn hit_limiter_rejects_hits_within_the_cooldown() {
+        let settings = Settings { hit_cooldown: 0.2, ..Default::default() };
+        let mut limiter = HitLimiter::default();
+        assert!(limiter.accept(millis(1000), &settings));
+        assert!(!limiter.accept(millis(1000), &settings));
+        assert!(!limiter.accept(millis(1150), &settings));
+        // a rejected hit does not start the cooldown again
+        assert!(limiter.accept(millis(1200), &settings));
+    }
+
+    #[test]
+    fn hit_limiter_takes_one_hit_per_pass() {
+        let settings = Settings { one_hit_per_pass: true, ..Default::default() };
+        let mut limiter = HitLimiter::default();
+        assert!(limiter.accept(millis(1000), &settings));
+        assert!(!limiter.accept(millis(2000), &settings));
+        limiter.hit_this_pass = false;
+        assert!(limiter.accept(millis(3000), &settings));
+    }
+
+    #[test]
+    fn position_before_rewinds_along_the_bar() {
+        assert_near(position_before(50.0, 100.0, 0.0, MIN, MAX), 50.0);
+        assert_near(position_before(50.0, 100.0, 0.25, MIN, MAX), 25.0);
+        assert_near(position_before(50.0, -100.0, 0.25, MIN, MAX), 75.0);
+    }
+
+    #[test]
+    fn position_before_unfolds_bounces() {
+        // moving left now, it came back from the right end 0.3 s ago
+        assert_near(position_before(80.0, -100.0, 0.3, MIN, MAX), 90.0);
+        // moving right now, it bounced off the left end 0.5 s ago
+        assert_near(position_before(-80.0, 100.0, 0.5, MIN, MAX), -70.0);
+        // a whole round trip brings it back where it is
+        assert_near(position_before(30.0, 100.0, 4.0, MIN, MAX), 30.0);
+    }
+
+    #[test]
+    fn position_before_looks_ahead_for_negative_time() {
+        assert_near(position_before(50.0, 100.0, -0.25, MIN, MAX), 75.0);
+        assert_near(position_before(90.0, 100.0, -0.2, MIN, MAX), 90.0);
+    }
+}
@@ -1,157 +1,179 @@
-use bevy::prelude::*;
-use bevy_ecs_ldtk::prelude::*;
-use bevy_hanabi::prelude::*;
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
 
-use crate::AppState;
+use bevy::{
+    prelude::*,
+    color::Mix,
+};
 
+use crate::AppState;
 use crate::ingame::{
-    TimingEvent,
+    PerfectEvent,
+    GoodEvent,
+    OkEvent,
+    BadEvent,
     ReversalEvent,
+    IngameState,
 };
 use crate::ingame::cue::Cue;
 use crate::ingame::bar::{
-    BAR_SIZE,
+    SIZE as BAR_SIZE,
     Bar,
 };
 
-const MAX_EFFECT_CAPACITY: u32 = 4096;
+// above the cue, so a burst is never hidden behind what caused it
+const PARTICLE_Z: f32 = 100.0;
+// golden ratio conjugate, spreads the particles of a burst without clumping or randomness
+const SCATTER: f32 = 0.618_034;
 
-#[derive(Component)]
-struct TimingEffect;
+const TIMING_GRADIENT: [(f32, LinearRgba); 2] = [
+    (0.0, LinearRgba::new(0.0, 0.7, 0.0, 1.0)),
+    (1.0, LinearRgba::new(0.0, 0.7, 0.0, 0.0)),
+];
+const TIMING_BURST: Burst = Burst {
+    count: 32,
+    spread: TAU,
+    speed: (20.0, 60.0),
+    size: (2.0, 4.0),
+    lifetime: 0.6,
+    gradient: &TIMING_GRADIENT,
+};
+const REVERSAL_GRADIENT: [(f32, LinearRgba); 3] = [
+    (0.0, LinearRgba::new(0.7, 0.0, 0.0, 1.0)),
+    (0.8, LinearRgba::new(0.7, 0.0, 0.0, 0.5)),
+    (1.0, LinearRgba::new(0.7, 0.0, 0.0, 0.0)),
+];
+const REVERSAL_BURST: Burst = Burst {
+    count: 16,
+    spread: FRAC_PI_2,
+    speed: (30.0, 80.0),
+    size: (3.0, 6.0),
+    lifetime: 0.8,
+    gradient: &REVERSAL_GRADIENT,
+};
+
+// How one burst of particles looks.
+// The gradient is keyed by the fraction of the lifetime, in increasing order.
+struct Burst {
+    count: usize,
+    // how far around the burst's direction the particles fly
+    spread: f32,
+    speed: (f32, f32),
+    size: (f32, f32),
+    lifetime: f32,
+    gradient: &'static [(f32, LinearRgba)],
+}
 
 #[derive(Component)]
-struct ReversalEffect;
+struct Particle {
+    velocity: Vec2,
+    age: f32,
+    lifetime: f32,
+    gradient: &'static [(f32, LinearRgba)],
+}
 
-fn setup(
-    ldtk_project_entities: Query<&Handle<LdtkProject>>,
-    mut effects: ResMut<Assets<EffectAsset>>,
-    mut commands: Commands,
+fn gradient_color(gradient: &[(f32, LinearRgba)], fraction: f32) -> LinearRgba {
+    let next = gradient.iter()
+        .position(|(key, _)| *key >= fraction)
+        .unwrap_or(gradient.len() - 1);
+    if next == 0 { return gradient[0].1 }
+
+    let (from_key, from) = gradient[next - 1];
+    let (to_key, to) = gradient[next];
+    from.mix(&to, ((fraction - from_key) / (to_key - from_key)).clamp(0.0, 1.0))
+}
+
+fn spawn_burst(
+    commands: &mut Commands,
+    burst: &Burst,
+    position: Vec2,
+    angle: f32,
 ) {
-    if !ldtk_project_entities.is_empty() { return }
-
-    // Timing effect
-    let mut gradient = Gradient::new();
-    gradient.add_key(0.0, Vec4::new(0.0, 0.7, 0.0, 1.0));
-    gradient.add_key(1.0, Vec4::new(0.0, 0.7, 0.0, 0.0));
-    let writer = ExprWriter::new();
-    let init_pos = SetPositionCircleModifier {
-        center: writer.lit(Vec3::ZERO).expr(),
-        axis: writer.lit(Vec3::Z).expr(),
-        radius: writer.lit(5.0).expr(),
-        dimension: ShapeDimension::Surface,
-    };
-    let init_vel = SetVelocityCircleModifier {
-        center: writer.lit(Vec3::ZERO).expr(),
-        axis: writer.lit(Vec3::Z).expr(),
-        speed: writer.lit(10.0).expr(),
-    };
-    let age = writer.lit(0.0).expr();
-    let init_age = SetAttributeModifier::new(Attribute::AGE, age);
-    let lifetime = writer.lit(1.0).expr();
-    let init_lifetime = SetAttributeModifier::new(Attribute::LIFETIME, lifetime);
-    let effect = EffectAsset::new(vec![MAX_EFFECT_CAPACITY], Spawner::once(1000.0.into(), true), writer.finish())
-        .with_name("timing_effect")
-        .init(init_pos)
-        .init(init_vel)
-        .init(init_age)
-        .init(init_lifetime)
-        .render(SizeOverLifetimeModifier {
-            gradient: Gradient::constant(Vec2::splat(1.0)),
-            screen_space_size: false,
-        })
-        .render(ColorOverLifetimeModifier { gradient });
-    let effect_handle = effects.add(effect);
-
-    commands.spawn((
-        ParticleEffectBundle {
-            effect: ParticleEffect::new(effect_handle).with_z_layer_2d(Some(10.0)),
-            ..default()
-        },
-        TimingEffect,
-    ))
-    .insert(Name::new("timing_effect"));
-    // Reversal effect
-    let mut gradient = Gradient::new();
-    gradient.add_key(0.0, Vec4::new(0.7, 0.0, 0.0, 1.0));
-    gradient.add_key(0.8, Vec4::new(0.7, 0.0, 0.0, 0.5));
-    gradient.add_key(1.0, Vec4::new(0.7, 0.0, 0.0, 0.0));
-    let writer = ExprWriter::new();
-    let init_pos = SetPositionCone3dModifier {
-        base_radius: writer.lit(2.0).expr(),
-        top_radius: writer.lit(10.0).expr(),
-        height: writer.lit(10.0).expr(),
-        dimension: ShapeDimension::Volume,
-    };
-    let init_vel = SetVelocitySphereModifier {
-        center: writer.lit(Vec3::ZERO).expr(),
-        speed: writer.lit(20.0).expr(),
-    };
-    let age = writer.lit(0.0).expr();
-    let init_age = SetAttributeModifier::new(Attribute::AGE, age);
-    let lifetime = writer.lit(1.0).expr();
-    let init_lifetime = SetAttributeModifier::new(Attribute::LIFETIME, lifetime);
-    let size = (writer.rand(ScalarType::Float) * writer.lit(1.5) + writer.lit(1.5)).expr();
-    let init_size = SetAttributeModifier::new(Attribute::SIZE, size);
-    let effect = EffectAsset::new(vec![MAX_EFFECT_CAPACITY], Spawner::once(50.0.into(), false), writer.finish())
-        .with_name("reversal_effect")
-        .init(init_pos)
-        .init(init_vel)
-        .init(init_age)
-        .init(init_lifetime)
-        .init(init_size)
-        .render(ColorOverLifetimeModifier { gradient });
-    let effect_handle = effects.add(effect);
-
-    commands.spawn((
-        ParticleEffectBundle {
-            effect: ParticleEffect::new(effect_handle).with_z_layer_2d(Some(10.0)),
-            ..default()
-        },
-        ReversalEffect,
-    ))
-    .insert(Name::new("reversal_effect"));
+    for index in 0..burst.count {
+        let scatter = (index as f32 * SCATTER).fract();
+        let angle = angle + burst.spread * ((index as f32 + 0.5) / burst.count as f32 - 0.5);
+        let speed = burst.speed.0 + (burst.speed.1 - burst.speed.0) * scatter;
+        let size = burst.size.0 + (burst.size.1 - burst.size.0) * (scatter * 2.0).fract();
+
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: gradient_color(burst.gradient, 0.0).into(),
+                    custom_size: Some(Vec2::splat(size)),
+                    ..Default::default()
+                },
+                transform: Transform::from_translation(position.extend(PARTICLE_Z)),
+                ..Default::default()
+            },
+            Particle {
+                velocity: Vec2::from_angle(angle) * speed,
+                age: 0.0,
+                lifetime: burst.lifetime,
+                gradient: burst.gradient,
+            },
+            StateScoped(AppState::Ingame),
+        ))
+        .insert(Name::new("particle"));
+    }
 }
 
-// Generates an effect at the cue position when the player sets the timing
-fn update_timing_effect(
-    mut timing_events: EventReader<TimingEvent>,
-    mut effect: Query<(&mut EffectSpawner, &mut Transform), (With<TimingEffect>, Without<Cue>)>,
-    cue_query: Query<&Transform, With<Cue>>,
+// Bursts where the cue was judged, so hits that were not judged make none
+fn timing_effect(
+    mut commands: Commands,
+    mut perfect_events: EventReader<PerfectEvent>,
+    mut good_events: EventReader<GoodEvent>,
+    mut ok_events: EventReader<OkEvent>,
+    mut bad_events: EventReader<BadEvent>,
 ) {
-    if timing_events.is_empty() { return }
-    timing_events.clear();
-
-    let Ok((mut spawner, mut effect_transform)) = effect.get_single_mut() else { return; };
-    let cue_transform = cue_query.single();
-    // spawn timing effect
-    effect_transform.translation = cue_transform.translation;
-    spawner.reset();
+    let positions = perfect_events.read().map(|event| event.position)
+        .chain(good_events.read().map(|event| event.position))
+        .chain(ok_events.read().map(|event| event.position))
+        .chain(bad_events.read().map(|event| event.position));
+
+    for position in positions {
+        spawn_burst(&mut commands, &TIMING_BURST, position, 0.0);
+    }
 }
 
-// Causes an effect when the cue is flipped
-fn update_reversal_effect(
+// Sprays back into the bar from the end the cue bounced off
+fn reversal_effect(
+    mut commands: Commands,
     mut reversal_events: EventReader<ReversalEvent>,
-    mut effect: Query<(&mut EffectSpawner, &mut Transform), (With<ReversalEffect>, Without<Bar>)>,
-    bar_query: Query<&Transform, (With<Bar>, Without<ReversalEffect>)>,
-    cue_query: Query<&Cue, With<Cue>>,
+    cue_query: Query<&Transform, (With<Cue>, Without<Bar>)>,
+    bar_query: Query<&Transform, (With<Bar>, Without<Cue>)>,
 ) {
-    if reversal_events.is_empty() { return }
-    reversal_events.clear();
+    let count = reversal_events.read().count();
+    let Ok(cue_transform) = cue_query.get_single() else { return };
+    let Ok(bar_transform) = bar_query.get_single() else { return };
 
-    let Ok((mut effect_spawner, mut effect_transform)) = effect.get_single_mut() else { return; };
-    let bar_transform = bar_query.single();
-    let bar_xy = bar_transform.translation.xy();
-    let cue_prop = cue_query.single();
-
-    let effect_transform_x = match cue_prop.toggle_move {
-        true => bar_xy.x - BAR_SIZE.x / 2.0,
-        false => bar_xy.x + BAR_SIZE.x / 2.0,
+    let bar_xy = bar_transform.translation.truncate();
+    let right = cue_transform.translation.x > bar_xy.x;
+    let (x, angle) = if right {
+        (bar_xy.x + BAR_SIZE.x / 2.0, PI)
+    } else {
+        (bar_xy.x - BAR_SIZE.x / 2.0, 0.0)
     };
-    let effect_rotation_z = if cue_prop.toggle_move { 1.5 } else { -1.5 };
-    // spawn reversal effect
-    effect_transform.translation = Vec3::new(effect_transform_x, bar_xy.y, 0.0);
-    effect_transform.rotation = Quat::from_rotation_z(effect_rotation_z);
-    effect_spawner.reset();
+    for _ in 0..count {
+        spawn_burst(&mut commands, &REVERSAL_BURST, Vec2::new(x, bar_xy.y), angle);
+    }
+}
+
+fn update(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds();
+
+    for (entity, mut particle, mut transform, mut sprite) in query.iter_mut() {
+        particle.age += delta;
+        if particle.age >= particle.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation += (particle.velocity * delta).extend(0.0);
+        sprite.color = gradient_color(particle.gradient, particle.age / particle.lifetime).into();
+    }
 }
 
 pub struct EffectsPlugin;
@@ -159,10 +181,113 @@ pub struct EffectsPlugin;
 impl Plugin for EffectsPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(OnEnter(AppState::Ingame), setup)
             .add_systems(Update, (
-                update_timing_effect,
-                update_reversal_effect,
-            ).run_if(in_state(AppState::Ingame)));
+                timing_effect,
+                reversal_effect,
+            ).run_if(in_state(IngameState::Playing)))
+            // particles freeze with the rest of the round while paused
+            .add_systems(Update, update.run_if(not(in_state(IngameState::Paused))))
+        ;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{
+        ecs::system::RunSystemOnce,
+        time::TimeUpdateStrategy,
+    };
+
+    use super::*;
+    use crate::ingame::Judgment;
+
+    const STEP: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn gradient_color_keeps_the_ends() {
+        assert_eq!(gradient_color(&REVERSAL_GRADIENT, 0.0), REVERSAL_GRADIENT[0].1);
+        assert_eq!(gradient_color(&REVERSAL_GRADIENT, 1.0), REVERSAL_GRADIENT[2].1);
+        // outside the keys the nearest end is held
+        assert_eq!(gradient_color(&REVERSAL_GRADIENT, -0.5), REVERSAL_GRADIENT[0].1);
+        assert_eq!(gradient_color(&REVERSAL_GRADIENT, 1.5), REVERSAL_GRADIENT[2].1);
+    }
+
+    #[test]
+    fn gradient_color_interpolates_between_keys() {
+        assert_eq!(gradient_color(&REVERSAL_GRADIENT, 0.8), REVERSAL_GRADIENT[1].1);
+        let color = gradient_color(&REVERSAL_GRADIENT, 0.4);
+        assert!((color.alpha - 0.75).abs() < 1e-5);
+        let color = gradient_color(&REVERSAL_GRADIENT, 0.9);
+        assert!((color.alpha - 0.25).abs() < 1e-5);
+        assert_eq!(color.red, 0.7);
+    }
+
+    fn particles(app: &mut App) -> Vec<(Vec3, f32)> {
+        app.world_mut()
+            .query_filtered::<(&Transform, &Sprite), With<Particle>>()
+            .iter(app.world())
+            .map(|(transform, sprite)| (transform.translation, sprite.color.alpha()))
+            .collect()
+    }
+
+    #[test]
+    fn particles_move_fade_and_despawn() {
+        let mut app = App::new();
+        app
+            .add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(STEP))
+            .add_systems(Update, update);
+        app.world_mut().run_system_once(|mut commands: Commands| {
+            spawn_burst(&mut commands, &TIMING_BURST, Vec2::ZERO, 0.0);
+        });
+
+        let spawned = particles(&mut app);
+        assert_eq!(spawned.len(), TIMING_BURST.count);
+        assert!(spawned.iter().all(|(position, alpha)| position.truncate() == Vec2::ZERO && *alpha == 1.0));
+
+        // the first update only starts the clock
+        app.update();
+        app.update();
+        app.update();
+        let moving = particles(&mut app);
+        assert_eq!(moving.len(), TIMING_BURST.count);
+        assert!(moving.iter().all(|(position, alpha)| position.truncate() != Vec2::ZERO && *alpha < 1.0));
+        assert!(moving.iter().all(|(position, _)| position.z == PARTICLE_Z));
+
+        let steps = (TIMING_BURST.lifetime / STEP.as_secs_f32()).ceil() as usize;
+        for _ in 0..steps { app.update() }
+        assert!(particles(&mut app).is_empty());
+    }
+
+    #[test]
+    fn timing_effect_bursts_at_each_judgment() {
+        let mut app = App::new();
+        app
+            .add_event::<PerfectEvent>()
+            .add_event::<GoodEvent>()
+            .add_event::<OkEvent>()
+            .add_event::<BadEvent>()
+            .add_systems(Update, timing_effect);
+        let judgment = |x: f32, index: usize| Judgment {
+            position: Vec2::new(x, 10.0),
+            timestamp: Duration::ZERO,
+            index,
+        };
+        app.world_mut().send_event(PerfectEvent(judgment(-20.0, 0)));
+        app.world_mut().send_event(BadEvent(judgment(30.0, 1)));
+        app.update();
+
+        let spawned = particles(&mut app);
+        assert_eq!(spawned.len(), TIMING_BURST.count * 2);
+        for x in [-20.0, 30.0] {
+            let at = spawned.iter().filter(|(position, _)| position.truncate() == Vec2::new(x, 10.0)).count();
+            assert_eq!(at, TIMING_BURST.count);
+        }
+
+        // a frame without judgments, as after a rejected hit, makes no burst
+        app.update();
+        assert_eq!(particles(&mut app).len(), TIMING_BURST.count * 2);
     }
 }
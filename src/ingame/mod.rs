@@ -1,11 +1,19 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 
+use crate::AppState;
+
 mod background;
 mod bar;
+mod camerafeedback;
 mod character;
+mod countdown;
 mod cue;
-// mod effects;
+mod effects;
 mod pausebutton;
+mod pausemenu;
+mod popups;
 mod scoreboard;
 mod sounds;
 mod timer;
@@ -15,20 +23,33 @@ const GRID_SIZE: f32 = 16.0;
 const GAMETIME_LIMIT: f32 = 10.0;
 const SCORE_CLEARPOINT: usize = 10;
 
-#[derive(Event, Default)]
-struct PerfectEvent;
+// Where and when a hit was judged, carried by every judgment event
+#[derive(Clone, Copy, Debug)]
+struct Judgment {
+    // where the cue was at the instant the hit was judged for
+    position: Vec2,
+    timestamp: Duration,
+    // order among the hits read in the same frame
+    index: usize,
+}
 
-#[derive(Event, Default)]
-struct GoodEvent;
+#[derive(Event, Deref)]
+struct PerfectEvent(Judgment);
 
-#[derive(Event, Default)]
-struct OkEvent;
+#[derive(Event, Deref)]
+struct GoodEvent(Judgment);
 
-#[derive(Event, Default)]
-struct BadEvent;
+#[derive(Event, Deref)]
+struct OkEvent(Judgment);
 
-#[derive(Event, Default)]
-struct TimingEvent;
+#[derive(Event, Deref)]
+struct BadEvent(Judgment);
+
+#[derive(Event)]
+struct TimingEvent {
+    // the frame the input was read in, see `ActionState::timestamp`
+    timestamp: Duration,
+}
 
 #[derive(Event, Default)]
 struct ReversalEvent;
@@ -36,11 +57,29 @@ struct ReversalEvent;
 #[derive(Resource)]
 struct GameTimer(Timer);
 
+// Entities of a round live as long as `AppState::Ingame`, whatever phase it is in
+#[derive(SubStates, Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[source(AppState = AppState::Ingame)]
+pub enum IngameState {
+    // every round opens with the countdown, while the cue and the timer wait
+    #[default]
+    Countdown,
+    Playing,
+    Paused,
+}
+
+fn restart(mut next_state: ResMut<NextState<AppState>>) {
+    println!("ingame: moved state to Ingame from Restart");
+    next_state.set(AppState::Ingame);
+}
+
 pub struct IngamePlugin;
 
 impl Plugin for IngamePlugin {
     fn build(&self, app: &mut App) {
         app
+            .add_sub_state::<IngameState>()
+            .enable_state_scoped_entities::<IngameState>()
             .add_event::<PerfectEvent>()
             .add_event::<GoodEvent>()
             .add_event::<OkEvent>()
@@ -50,12 +89,17 @@ impl Plugin for IngamePlugin {
             .insert_resource(
                 GameTimer(Timer::from_seconds(GAMETIME_LIMIT, TimerMode::Once))
             )
+            .add_systems(OnEnter(AppState::Restart), restart)
             .add_plugins(background::BackgroundPlugin)
             .add_plugins(bar::BarPlugin)
+            .add_plugins(camerafeedback::CameraFeedbackPlugin)
             .add_plugins(character::CharacterPlugin)
+            .add_plugins(countdown::CountdownPlugin)
             .add_plugins(cue::CuePlugin)
-            // .add_plugins(effects::EffectsPlugin)
+            .add_plugins(effects::EffectsPlugin)
             .add_plugins(pausebutton::PauseButtonPlugin)
+            .add_plugins(pausemenu::PauseMenuPlugin)
+            .add_plugins(popups::PopupsPlugin)
             .add_plugins(scoreboard::ScoreboardPlugin)
             .add_plugins(sounds::SoundsPlugin)
             .add_plugins(timer::GameTimerPlugin)
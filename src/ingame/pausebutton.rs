@@ -1,46 +1,44 @@
 use bevy::{
     prelude::*,
-    window::PrimaryWindow,
+    window::WindowFocused,
 };
 
 use crate::{
     WINDOW_SIZE,
     CURSOR_RANGE,
     PATH_IMAGE_PAUSEBUTTON,
+    PATH_ANIMATION_PAUSEBUTTON,
     AppState,
-    Config,
+    MenuState,
+};
+use crate::animation::{
+    texture_atlas,
+    AnimationPlayer,
+};
+use crate::input::{
+    Action,
+    ActionState,
+};
+use crate::ingame::IngameState;
+use crate::settings::Settings;
+use crate::spritebutton::{
+    ButtonPressed,
+    HitShape,
+    SpriteButton,
 };
 
-const IMAGE_SIZE: u32 = 64;
-const IMAGE_COLUMN: u32 = 2;
-const IMAGE_ROW: u32 = 1;
 const SIZE: f32 = 32.0;
 const PADDING: f32 = 5.0;
 
 #[derive(Component)]
-struct PauseButton {
-    first: usize,
-    last: usize,
-}
+struct PauseButton;
 
 fn setup(
     mut commands: Commands,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     asset_server: Res<AssetServer>,
-    config: Res<Config>,
 ) {
-    if !config.setup_ingame { return }
-
     println!("pausebutton: setup");
-    let layout = TextureAtlasLayout::from_grid(
-        UVec2::splat(IMAGE_SIZE),
-        IMAGE_COLUMN,
-        IMAGE_ROW,
-        None,
-        None,
-    );
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-    let animation_indices = PauseButton { first: 0, last: 1 };
+    // the corner of the virtual screen stays the corner of the view at any window size
     let (x, y, z) = (
         WINDOW_SIZE.x / 2.0 - SIZE / 2.0 - PADDING, 
         -WINDOW_SIZE.y / 2.0 + SIZE / 2.0 + PADDING, 
@@ -57,60 +55,68 @@ fn setup(
             transform: Transform::from_xyz(x, y, z),
             ..Default::default()
         },
-        TextureAtlas {
-            layout: texture_atlas_layout,
-            index: animation_indices.first,
-        },
-        animation_indices,
+        texture_atlas(&asset_server, PATH_ANIMATION_PAUSEBUTTON),
+        AnimationPlayer::new(asset_server.load(PATH_ANIMATION_PAUSEBUTTON), "play"),
+        SpriteButton::new(HitShape::Circle(SIZE - CURSOR_RANGE)),
+        PauseButton,
+        StateScoped(AppState::Ingame),
     ))
     .insert(Name::new("pausebutton"));
 }
 
 fn update(
-    mut query: Query<(&Transform, &PauseButton, &mut TextureAtlas), With<PauseButton>>,
-    mut next_state: ResMut<NextState<AppState>>,
-    mouse_events: Res<ButtonInput<MouseButton>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    mut config: ResMut<Config>,
+    query: Query<Entity, With<PauseButton>>,
+    mut next_state: ResMut<NextState<IngameState>>,
+    mut button_events: EventReader<ButtonPressed>,
+    action_state: Res<ActionState>,
+    state: Res<State<IngameState>>,
 ) {
-    if !mouse_events.just_pressed(MouseButton::Left) { return }
-
-    let window = window_query.single();
-    let mut cursor_pos = window.cursor_position().unwrap();
-    let Ok((transform, prop, mut atlas)) = query.get_single_mut() else { return };
-    let pausebutton_pos = transform.translation.truncate();
-    cursor_pos = Vec2::new(
-        cursor_pos.x - WINDOW_SIZE.x / 2.0,
-        -cursor_pos.y + WINDOW_SIZE.y / 2.0
-    );
-
-    let distance = cursor_pos.distance(pausebutton_pos);
+    let Ok(entity) = query.get_single() else { return };
+    let clicked = button_events.read().any(|event| event.entity == entity);
+    if !clicked && !action_state.just_pressed(Action::Pause) { return }
 
-    if distance < SIZE - CURSOR_RANGE {
-        if atlas.index == prop.first {
-            println!("pausebutton: toggled");
-            atlas.index = prop.last;
-            println!("pausebutton: moved state to Pause from Ingame");
-            next_state.set(AppState::Pause);
-        } else {
-            if config.setup_ingame {
-                println!("pausebutton: change config.setup_ingame to false");
-                config.setup_ingame = false;
-            }
-            println!("pausebutton: toggled");
-            atlas.index = prop.first;
-            println!("pausebutton: moved state to Ingame from Pause");
-            next_state.set(AppState::Ingame);
-        }
+    println!("pausebutton: toggled");
+    if *state.get() == IngameState::Paused {
+        // resuming counts down first so the player can catch the rhythm again
+        println!("pausebutton: moved state to Countdown from Paused");
+        next_state.set(IngameState::Countdown);
+    } else {
+        println!("pausebutton: moved state to Paused from {:?}", state.get());
+        next_state.set(IngameState::Paused);
     }
 }
 
-fn despawn(
-    mut commands: Commands,
-    query: Query<Entity, With<PauseButton>>,
+// The options screen opened from the pause menu covers the button
+fn enable(
+    mut query: Query<&mut SpriteButton, With<PauseButton>>,
+    menu_state: Res<State<MenuState>>,
+) {
+    let Ok(mut button) = query.get_single_mut() else { return };
+    button.disabled = *menu_state.get() != MenuState::Closed;
+}
+
+fn focus(
+    mut events: EventReader<WindowFocused>,
+    mut next_state: ResMut<NextState<IngameState>>,
+    state: Res<State<IngameState>>,
+    settings: Res<Settings>,
 ) {
-    println!("pausebutton: despawn");
-    for entity in query.iter() { commands.entity(entity).despawn() }
+    let Some(event) = events.read().last() else { return };
+    if event.focused || !settings.auto_pause || *state.get() == IngameState::Paused { return }
+
+    // the round would go on unseen, so it waits in the pause menu instead
+    println!("pausebutton: moved state to Paused from {:?} on focus loss", state.get());
+    next_state.set(IngameState::Paused);
+}
+
+fn pause(mut query: Query<&mut AnimationPlayer, With<PauseButton>>) {
+    let Ok(mut player) = query.get_single_mut() else { return };
+    player.play("pause");
+}
+
+fn resume(mut query: Query<&mut AnimationPlayer, With<PauseButton>>) {
+    let Ok(mut player) = query.get_single_mut() else { return };
+    player.play("play");
 }
 
 pub struct PauseButtonPlugin;
@@ -119,9 +125,17 @@ impl Plugin for PauseButtonPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_systems(OnEnter(AppState::Ingame), setup)
-            .add_systems(Update, update.run_if(in_state(AppState::Ingame)))
-            .add_systems(Update, update.run_if(in_state(AppState::Pause)))
-            .add_systems(OnEnter(AppState::Mainmenu), despawn)
+            .add_systems(OnEnter(IngameState::Playing), resume)
+            .add_systems(OnEnter(IngameState::Countdown), resume)
+            .add_systems(OnEnter(IngameState::Paused), pause)
+            // the options screen opened from the pause menu has its own way back
+            .add_systems(Update, update.run_if(
+                in_state(AppState::Ingame).and_then(in_state(MenuState::Closed))
+            ))
+            .add_systems(Update, enable.run_if(
+                in_state(AppState::Ingame).and_then(state_changed::<MenuState>)
+            ))
+            .add_systems(Update, focus.run_if(in_state(AppState::Ingame)))
         ;
     }
 }
@@ -0,0 +1,195 @@
+use bevy::prelude::*;
+
+use crate::{
+    AppState,
+    MenuState,
+};
+use crate::input::{
+    Action,
+    ActionState,
+};
+use crate::locale::LocalizedText;
+use crate::menu::{
+    button_bundle,
+    MenuButton,
+    MenuPressed,
+};
+use crate::ingame::IngameState;
+
+const PAUSE_SIZE: f32 = 24.0;
+const BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+const BOARD_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const BOARD_PADDING: Val = Val::Px(20.0);
+const TEXT_SIZE: f32 = 16.0;
+const TEXT_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+const TEXT_PADDING: Val = Val::Px(6.0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PauseItem {
+    Resume,
+    Restart,
+    Options,
+    Quit,
+}
+
+const ITEMS: [PauseItem; 4] = [
+    PauseItem::Resume,
+    PauseItem::Restart,
+    PauseItem::Options,
+    PauseItem::Quit,
+];
+
+impl PauseItem {
+    fn text_key(&self) -> &'static str {
+        match self {
+            PauseItem::Resume => "pausemenu.resume",
+            PauseItem::Restart => "pausemenu.restart",
+            PauseItem::Options => "pausemenu.options",
+            PauseItem::Quit => "pausemenu.quit",
+        }
+    }
+}
+
+// The menu is shown while paused, and hidden while the options screen covers it
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct PauseMenuOpen;
+
+impl ComputedStates for PauseMenuOpen {
+    type SourceStates = (IngameState, MenuState);
+
+    fn compute((ingame_state, menu_state): (IngameState, MenuState)) -> Option<Self> {
+        (ingame_state == IngameState::Paused && menu_state == MenuState::Closed).then_some(PauseMenuOpen)
+    }
+}
+
+#[derive(Component)]
+struct PauseMenu;
+
+#[derive(Component)]
+struct PauseMenuButton(PauseItem);
+
+fn setup(mut commands: Commands) {
+    println!("pausemenu: setup");
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: BACKGROUND_COLOR.into(),
+            ..Default::default()
+        },
+        PauseMenu,
+        StateScoped(PauseMenuOpen),
+    ))
+    .insert(Name::new("pausemenu"))
+    .with_children(|parent| {
+        parent.spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(BOARD_PADDING),
+                ..Default::default()
+            },
+            background_color: BOARD_COLOR.into(),
+            ..Default::default()
+        })
+        .insert(Name::new("board"))
+        .with_children(|parent| {
+            // title
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: PAUSE_SIZE,
+                        color: TEXT_COLOR,
+                        ..Default::default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(TEXT_PADDING),
+                    ..Default::default()
+                }),
+                LocalizedText("pausemenu.title"),
+            ))
+            .insert(Name::new("title"));
+            // items
+            for (index, item) in ITEMS.into_iter().enumerate() {
+                parent.spawn((
+                    button_bundle(),
+                    MenuButton(index),
+                    PauseMenuButton(item),
+                ))
+                .insert(Name::new(format!("{:?}", item)))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: TEXT_SIZE,
+                                color: TEXT_COLOR,
+                                ..Default::default()
+                            },
+                        ),
+                        LocalizedText(item.text_key()),
+                    ));
+                });
+            }
+        });
+    });
+}
+
+fn update(
+    mut events: EventReader<MenuPressed>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut next_ingame_state: ResMut<NextState<IngameState>>,
+    mut next_menu_state: ResMut<NextState<MenuState>>,
+    query: Query<&PauseMenuButton>,
+    action_state: Res<ActionState>,
+) {
+    let mut pressed = events.read()
+        .filter_map(|event| query.get(event.0).ok())
+        .map(|button| button.0)
+        .last();
+    if action_state.just_pressed(Action::Back) {
+        pressed = Some(PauseItem::Resume);
+    }
+
+    let Some(item) = pressed else { return };
+    match item {
+        PauseItem::Resume => {
+            println!("pausemenu: moved state to Countdown from Paused");
+            next_ingame_state.set(IngameState::Countdown);
+        }
+        PauseItem::Restart => {
+            // leaving the round clears everything in it, as a retry from the result screen does
+            println!("pausemenu: moved state to Restart from Ingame");
+            next_app_state.set(AppState::Restart);
+        }
+        PauseItem::Options => {
+            println!("pausemenu: moved state to Options from Closed");
+            next_menu_state.set(MenuState::Options);
+        }
+        PauseItem::Quit => {
+            println!("pausemenu: moved state to Mainmenu from Ingame");
+            next_app_state.set(AppState::Mainmenu);
+        }
+    }
+}
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_computed_state::<PauseMenuOpen>()
+            .enable_state_scoped_entities::<PauseMenuOpen>()
+            .add_systems(OnEnter(PauseMenuOpen), setup)
+            .add_systems(Update, update.run_if(in_state(PauseMenuOpen)))
+        ;
+    }
+}
@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::ingame::{
+    GRID_SIZE,
+    Judgment,
+    PerfectEvent,
+    GoodEvent,
+    OkEvent,
+    BadEvent,
+    IngameState,
+};
+
+const TEXT_SIZE: f32 = 32.0;
+// above the cue and its particles
+const POPUP_Z: f32 = 101.0;
+const LIFETIME: f32 = 0.8;
+const RISE_SPEED: f32 = 60.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Grade {
+    Perfect,
+    Good,
+    Ok,
+    Bad,
+}
+
+impl Grade {
+    fn text(&self) -> &'static str {
+        match self {
+            Grade::Perfect => "PERFECT",
+            Grade::Good => "GOOD",
+            Grade::Ok => "OK",
+            Grade::Bad => "BAD",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Grade::Perfect => Color::srgb(0.9, 0.6, 0.0),
+            Grade::Good => Color::srgb(0.0, 0.6, 0.0),
+            Grade::Ok => Color::srgb(0.0, 0.4, 0.8),
+            Grade::Bad => Color::srgb(0.6, 0.1, 0.1),
+        }
+    }
+}
+
+#[derive(Component)]
+struct Popup {
+    age: f32,
+    color: Color,
+}
+
+// One text per judged hit, where the cue was judged,
+// stacked in the order of the hits so those of the same frame do not overlap
+fn spawn(
+    mut commands: Commands,
+    mut perfect_events: EventReader<PerfectEvent>,
+    mut good_events: EventReader<GoodEvent>,
+    mut ok_events: EventReader<OkEvent>,
+    mut bad_events: EventReader<BadEvent>,
+) {
+    let mut judged: Vec<(Grade, Judgment)> = perfect_events.read()
+        .map(|event| (Grade::Perfect, **event))
+        .chain(good_events.read().map(|event| (Grade::Good, **event)))
+        .chain(ok_events.read().map(|event| (Grade::Ok, **event)))
+        .chain(bad_events.read().map(|event| (Grade::Bad, **event)))
+        .collect();
+    judged.sort_by_key(|(_, judgment)| (judgment.timestamp, judgment.index));
+
+    for (stack, (grade, judgment)) in judged.into_iter().enumerate() {
+        let position = judgment.position + Vec2::new(0.0, GRID_SIZE * (2.0 + stack as f32 * 2.0));
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    grade.text(),
+                    TextStyle {
+                        font_size: TEXT_SIZE,
+                        color: grade.color(),
+                        ..Default::default()
+                    },
+                ),
+                transform: Transform::from_translation(position.extend(POPUP_Z)),
+                ..Default::default()
+            },
+            Popup { age: 0.0, color: grade.color() },
+            StateScoped(AppState::Ingame),
+        ))
+        .insert(Name::new("popup"));
+    }
+}
+
+// Floats up and fades out, then goes away
+fn update(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Popup, &mut Transform, &mut Text)>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds();
+
+    for (entity, mut popup, mut transform, mut text) in query.iter_mut() {
+        popup.age += delta;
+        if popup.age >= LIFETIME {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation.y += RISE_SPEED * delta;
+        let alpha = 1.0 - popup.age / LIFETIME;
+        text.sections[0].style.color = popup.color.with_alpha(alpha);
+    }
+}
+
+pub struct PopupsPlugin;
+
+impl Plugin for PopupsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, spawn.run_if(in_state(IngameState::Playing)))
+            // popups wait with the rest of the round while paused
+            .add_systems(Update, update.run_if(not(in_state(IngameState::Paused))))
+        ;
+    }
+}
@@ -1,22 +1,20 @@
 use bevy::prelude::*;
 
 use crate::{
-    PATH_FONT,
     AppState,
-    Config,
     Score,
 };
 
+use crate::locale::Locale;
 use crate::ingame::{
     PerfectEvent,
     GoodEvent,
     OkEvent,
     BadEvent,
     GameTimer,
+    IngameState,
 };
 
-const SCORE_TEXT: &str = "スコア: ";
-const TIME_TEXT: &str = " | タイム: ";
 const PERFECT_POINT: usize = 3;
 const GOOD_POINT: usize = 2;
 const OK_POINT: usize = 1;
@@ -28,41 +26,29 @@ const TEXT_PADDING: Val = Val::Px(5.0);
 #[derive(Component)]
 struct ScoreboardUi;
 
-fn setup(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    config: Res<Config>,
-) {
-    if !config.setup_ingame { return }
-
+fn setup(mut commands: Commands) {
     println!("scoreboard: setup");
     commands.spawn((
         TextBundle::from_sections([
-            TextSection::new(
-                SCORE_TEXT,
-                TextStyle {
-                    font: asset_server.load(PATH_FONT),
-                    font_size: TEXT_SIZE,
-                    color: TEXT_COLOR,
-                },
-            ),
             TextSection::from_style(TextStyle {
-                font: asset_server.load(PATH_FONT),
                 font_size: TEXT_SIZE,
                 color: TEXT_COLOR,
+                ..Default::default()
+            }),
+            TextSection::from_style(TextStyle {
+                font_size: TEXT_SIZE,
+                color: TEXT_COLOR,
+                ..Default::default()
+            }),
+            TextSection::from_style(TextStyle {
+                font_size: TEXT_SIZE,
+                color: TEXT_COLOR,
+                ..Default::default()
             }),
-            TextSection::new(
-                TIME_TEXT,
-                TextStyle {
-                    font: asset_server.load(PATH_FONT),
-                    font_size: TEXT_SIZE,
-                    color: TEXT_COLOR,
-                },
-            ),
             TextSection::from_style(TextStyle {
-                font: asset_server.load(PATH_FONT),
                 font_size: TEXT_SIZE,
                 color: TEXT_COLOR,
+                ..Default::default()
             }),
         ])
         .with_style(Style {
@@ -72,6 +58,7 @@ fn setup(
             ..Default::default()
         }),
         ScoreboardUi,
+        StateScoped(AppState::Ingame),
     ))
     .insert(Name::new("scoreboard"));
 } 
@@ -80,9 +67,14 @@ fn update(
     mut query: Query<&mut Text, With<ScoreboardUi>>,
     score: Res<Score>,
     timer: ResMut<GameTimer>,
+    locale: Locale,
 ) {
     let mut text = query.single_mut();
-    // write score and timer
+    // write labels, score and timer
+    if locale.is_changed() || text.is_added() {
+        text.sections[0].value = locale.get("common.score");
+        text.sections[2].value = locale.get("scoreboard.time");
+    }
     text.sections[1].value = score.to_string();
     text.sections[3].value = timer.0.remaining_secs().round().to_string();
 }
@@ -94,24 +86,20 @@ fn score_points(
     mut ok_events: EventReader<OkEvent>,
     mut bad_events: EventReader<BadEvent>,
 ) {
-    if !perfect_events.is_empty() {
+    for _ in perfect_events.read() {
         println!("scoreboard: score increase {}", PERFECT_POINT);
-        perfect_events.clear();
         **score += PERFECT_POINT;
     }
-    if !good_events.is_empty() {
+    for _ in good_events.read() {
         println!("scoreboard: score increase {}", GOOD_POINT);
-        good_events.clear();
         **score += GOOD_POINT;
     }
-    if !ok_events.is_empty() {
+    for _ in ok_events.read() {
         println!("scoreboard: score increase {}", OK_POINT);
-        ok_events.clear();
         **score += OK_POINT;
     }
-    if !bad_events.is_empty() {
-        println!("scoreboard: score decrease {}", PERFECT_POINT);
-        bad_events.clear();
+    for _ in bad_events.read() {
+        println!("scoreboard: score decrease {}", BAD_POINT);
         if **score > BAD_POINT { **score -= BAD_POINT } else { **score = 0 };
     }
 }
@@ -120,27 +108,15 @@ fn reset_score(mut score: ResMut<Score>) {
     **score = 0;
 }
 
-fn despawn(
-    mut commands: Commands,
-    query: Query<Entity, With<ScoreboardUi>>,
-) {
-    println!("scoreboard: despawn");
-    for entity in query.iter() { commands.entity(entity).despawn() }
-}
-
 pub struct ScoreboardPlugin;
 
 impl Plugin for ScoreboardPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(OnEnter(AppState::Ingame), setup)
-            .add_systems(Update, (
-                update,
-                score_points,
-            ).run_if(in_state(AppState::Ingame)))
-            .add_systems(OnEnter(AppState::Mainmenu), despawn)
-            .add_systems(OnExit(AppState::Gameover), reset_score)
-            .add_systems(OnExit(AppState::Gameclear), reset_score)
+            // the score stays for the result screen until the next round starts
+            .add_systems(OnEnter(AppState::Ingame), (reset_score, setup))
+            .add_systems(Update, update.run_if(in_state(AppState::Ingame)))
+            .add_systems(Update, score_points.run_if(in_state(IngameState::Playing)))
         ;
     }
 }
@@ -6,7 +6,10 @@ use crate::{
     PATH_SOUND_PERFECT,
     PATH_SOUND_REVERSAL,
     AppState,
-    Config,
+};
+use crate::audio::{
+    AudioBus,
+    SoundPlayer,
 };
 
 use crate::ingame::{
@@ -14,6 +17,7 @@ use crate::ingame::{
     OkEvent,
     PerfectEvent,
     ReversalEvent,
+    IngameState,
 };
 
 #[derive(Resource, Deref)]
@@ -31,10 +35,7 @@ struct ReversalSound(Handle<AudioSource>);
 fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    config: Res<Config>,
 ) {
-    if !config.setup_ingame { return }
-
     println!("sounds: setup");
     let perfect_sound = asset_server.load(PATH_SOUND_PERFECT);
     commands.insert_resource(PerfectSound(perfect_sound));
@@ -51,58 +52,47 @@ fn setup(
 
 fn play_perfect_sound(
     mut events: EventReader<PerfectEvent>,
-    mut commands: Commands,
+    mut sound_player: SoundPlayer,
     sound: Res<PerfectSound>,
 ) {
-    if events.is_empty() { return }
-    events.clear();
-    println!("sounds: perfect");
-    commands.spawn(AudioBundle {
-        source: sound.clone(),
-        settings: PlaybackSettings::DESPAWN,
-    });
+    // one sound per judged hit, so a double tap is heard twice
+    for _ in events.read() {
+        println!("sounds: perfect");
+        sound_player.play(sound.clone(), AudioBus::Sfx);
+    }
 }
 
 fn play_good_sound(
     mut events: EventReader<GoodEvent>,
-    mut commands: Commands,
+    mut sound_player: SoundPlayer,
     sound: Res<GoodSound>,
 ) {
-    if events.is_empty() { return }
-    events.clear();
-    println!("sounds: good");
-    commands.spawn(AudioBundle {
-        source: sound.clone(),
-        settings: PlaybackSettings::DESPAWN,
-    });
+    for _ in events.read() {
+        println!("sounds: good");
+        sound_player.play(sound.clone(), AudioBus::Sfx);
+    }
 }
 
 fn play_ok_sound(
     mut events: EventReader<OkEvent>,
-    mut commands: Commands,
+    mut sound_player: SoundPlayer,
     sound: Res<OkSound>,
 ) {
-    if events.is_empty() { return }
-    events.clear();
-    println!("sounds: ok");
-    commands.spawn(AudioBundle {
-        source: sound.clone(),
-        settings: PlaybackSettings::DESPAWN,
-    });
+    for _ in events.read() {
+        println!("sounds: ok");
+        sound_player.play(sound.clone(), AudioBus::Sfx);
+    }
 }
 
 fn play_reversal_sound(
     mut events: EventReader<ReversalEvent>,
-    mut commands: Commands,
+    mut sound_player: SoundPlayer,
     sound: Res<ReversalSound>,
 ) {
     if events.is_empty() { return }
     events.clear();
     // play reversal sound
-    commands.spawn(AudioBundle {
-        source: sound.clone(),
-        settings: PlaybackSettings::DESPAWN,
-    });
+    sound_player.play(sound.clone(), AudioBus::Sfx);
 }
 
 pub struct SoundsPlugin;
@@ -116,7 +106,7 @@ impl Plugin for SoundsPlugin {
                 play_ok_sound,
                 play_perfect_sound,
                 play_reversal_sound,
-            ).run_if(in_state(AppState::Ingame)))
+            ).run_if(in_state(IngameState::Playing)))
         ;
     }
 }
@@ -7,6 +7,7 @@ use crate::{
 use crate::ingame::{
     SCORE_CLEARPOINT,
     GameTimer,
+    IngameState,
 };
 
 fn update(
@@ -16,8 +17,6 @@ fn update(
     score: Res<Score>,
 ) {
     if timer.0.tick(time.delta()).just_finished() {
-        println!("timer: reset");
-        timer.0.reset();
         if **score < SCORE_CLEARPOINT {
             println!("timer: moved state to Gameover from Ingame");
             next_state.set(AppState::Gameover);
@@ -28,12 +27,18 @@ fn update(
    }
 }
 
+fn reset(mut timer: ResMut<GameTimer>) {
+    println!("timer: reset");
+    timer.0.reset();
+}
+
 pub struct GameTimerPlugin;
 
 impl Plugin for GameTimerPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Update, update.run_if(in_state(AppState::Ingame)))
+            .add_systems(OnEnter(AppState::Ingame), reset)
+            .add_systems(Update, update.run_if(in_state(IngameState::Playing)))
         ;
     }
 }
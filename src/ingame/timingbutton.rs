@@ -1,49 +1,41 @@
-use bevy::{
-    prelude::*,
-    window::PrimaryWindow,
-};
+use bevy::prelude::*;
 
 use crate::{
     CURSOR_RANGE,
     PATH_IMAGE_TIMINGBUTTON,
+    PATH_ANIMATION_TIMINGBUTTON,
     AppState,
-    Config,
 };
-use crate::ingame::TimingEvent;
+use crate::animation::{
+    texture_atlas,
+    AnimationPlayer,
+};
+use crate::input::{
+    Action,
+    ActionState,
+};
+use crate::ingame::{
+    IngameState,
+    TimingEvent,
+};
+use crate::spritebutton::{
+    ButtonFrames,
+    ButtonPressed,
+    HitShape,
+    SpriteButton,
+};
 
-const IMAGE_SIZE: u32 = 64;
-const IMAGE_COLUMN: u32 = 2;
-const IMAGE_ROW: u32 = 1;
 const SIZE: f32 = 64.0;
+const PRESSED_FRAME: usize = 1;
 
 #[derive(Component)]
-struct TimingButton {
-    first: usize,
-    last: usize,
-}
-
-#[derive(Component, Deref, DerefMut)]
-struct AnimationTimer(Timer);
+struct TimingButton;
 
 fn setup(
     mut commands: Commands,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     asset_server: Res<AssetServer>,
-    config: Res<Config>,
 ) {
-    if !config.setup_ingame { return }
-
     println!("timingbutton: setup");
-    let layout = TextureAtlasLayout::from_grid(
-        UVec2::splat(IMAGE_SIZE),
-        IMAGE_COLUMN,
-        IMAGE_ROW,
-        None,
-        None,
-    );
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-    let animation_indices = TimingButton { first: 0, last: 1 };
-
     commands.spawn((
         SpriteBundle {
             sprite: Sprite {
@@ -54,62 +46,51 @@ fn setup(
             transform: Transform::from_xyz(0.0, 0.0, 0.0),
             ..Default::default()
         },
-        TextureAtlas {
-            layout: texture_atlas_layout,
-            index: animation_indices.first,
-        },
-        animation_indices,
-        AnimationTimer(Timer::from_seconds(0.5, TimerMode::Repeating))
+        texture_atlas(&asset_server, PATH_ANIMATION_TIMINGBUTTON),
+        AnimationPlayer::new(asset_server.load(PATH_ANIMATION_TIMINGBUTTON), "idle"),
+        SpriteButton::new(HitShape::Circle(SIZE - CURSOR_RANGE)).with_frames(ButtonFrames {
+            pressed: Some(PRESSED_FRAME),
+            ..Default::default()
+        }),
+        TimingButton,
+        StateScoped(AppState::Ingame),
     ))
     .insert(Name::new("timingbutton"));
 }
 
 fn update(
-    mut query: Query<(&mut TextureAtlas, &Transform, &TimingButton), With<TimingButton>>,
+    mut query: Query<(Entity, &mut AnimationPlayer), With<TimingButton>>,
     mut timing_events: EventWriter<TimingEvent>,
-    mouse_events: Res<ButtonInput<MouseButton>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut button_events: EventReader<ButtonPressed>,
+    action_state: Res<ActionState>,
 ) {
-    if !mouse_events.just_pressed(MouseButton::Left) { return }
-
-    let window = window_query.single();
-    let mut cursor_pos = window.cursor_position().unwrap();
-    let Ok((mut atlas, transform, prop)) = query.get_single_mut() else { return };
-    let timingbtn_pos = transform.translation.truncate();
-    cursor_pos = Vec2::new(
-        cursor_pos.x - window.width() / 2.0, 
-        -cursor_pos.y + window.height() / 2.0,
-    );
-
-    let distance = cursor_pos.distance(timingbtn_pos);
-
-    if distance < SIZE - CURSOR_RANGE {
-        timing_events.send_default();
-        println!("timingbutton: push");
-        atlas.index = prop.last;
-     }
-}
-
-fn animation(
-    mut query: Query<(&mut TextureAtlas, &mut AnimationTimer, &TimingButton), With<TimingButton>>,
-    time: Res<Time>,
-) {
-    let Ok((mut atlas, mut timer, prop)) = query.get_single_mut() else { return };
-
-    if atlas.index == prop.first { return }
-
-    timer.tick(time.delta());
-    if timer.just_finished() {
-        atlas.index = prop.first;
-    }
+    let Ok((entity, mut player)) = query.get_single_mut() else { return };
+    // every press is sent on its own, so the judgment decides what a double tap is worth
+    let mut push = |count: usize| {
+        if count == 0 { return }
+        for _ in 0..count {
+            timing_events.send(TimingEvent { timestamp: action_state.timestamp() });
+        }
+        println!("timingbutton: push x{}", count);
+        player.play("push");
+    };
+
+    // every new finger is counted, so another finger may be holding the pause button
+    let hits: usize = button_events.read()
+        .filter(|event| event.entity == entity)
+        .map(|event| event.count)
+        .sum();
+    // keys and clicks of the same frame are all judged
+    push(action_state.presses(Action::Hit) + hits);
 }
 
-fn despawn(
-    mut commands: Commands,
-    query: Query<Entity, With<TimingButton>>,
+// The button only answers while the round is running
+fn enable(
+    mut query: Query<&mut SpriteButton, With<TimingButton>>,
+    state: Res<State<IngameState>>,
 ) {
-    println!("timingbutton: despawn");
-    for entity in query.iter() { commands.entity(entity).despawn() }
+    let Ok(mut button) = query.get_single_mut() else { return };
+    button.disabled = *state.get() != IngameState::Playing;
 }
 
 pub struct TimingButtonPlugin;
@@ -118,11 +99,8 @@ impl Plugin for TimingButtonPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_systems(OnEnter(AppState::Ingame), setup)
-            .add_systems(Update, (
-                update,
-                animation,
-            ).run_if(in_state(AppState::Ingame)))
-            .add_systems(OnEnter(AppState::Mainmenu), despawn)
+            .add_systems(Update, enable.run_if(state_changed::<IngameState>))
+            .add_systems(Update, update.run_if(in_state(IngameState::Playing)))
         ;
     }
 }
@@ -0,0 +1,276 @@
+use std::time::Duration;
+
+use bevy::{
+    prelude::*,
+    input::{
+        mouse::MouseButtonInput,
+        ButtonState,
+        InputSystem,
+    },
+    utils::HashMap,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    Hit,
+    Pause,
+    Confirm,
+    Back,
+    Retry,
+    Options,
+    Mute,
+    Fullscreen,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+pub const ACTIONS: [Action; 12] = [
+    Action::Hit,
+    Action::Pause,
+    Action::Confirm,
+    Action::Back,
+    Action::Retry,
+    Action::Options,
+    Action::Mute,
+    Action::Fullscreen,
+    Action::Up,
+    Action::Down,
+    Action::Left,
+    Action::Right,
+];
+
+impl Action {
+    // key of the name in the string tables
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            Action::Hit => "action.hit",
+            Action::Pause => "action.pause",
+            Action::Confirm => "action.confirm",
+            Action::Back => "action.back",
+            Action::Retry => "action.retry",
+            Action::Options => "action.options",
+            Action::Mute => "action.mute",
+            Action::Fullscreen => "action.fullscreen",
+            Action::Up => "action.up",
+            Action::Down => "action.down",
+            Action::Left => "action.left",
+            Action::Right => "action.right",
+        }
+    }
+
+    fn default_bindings(&self) -> Vec<Binding> {
+        use Binding::*;
+
+        match self {
+            Action::Hit => vec![
+                Key(KeyCode::Space),
+                Key(KeyCode::Enter),
+                Gamepad(GamepadButtonType::South),
+            ],
+            Action::Pause => vec![
+                Key(KeyCode::KeyP),
+                Key(KeyCode::Escape),
+                Gamepad(GamepadButtonType::Start),
+            ],
+            Action::Confirm => vec![
+                Key(KeyCode::Enter),
+                Mouse(MouseButton::Left),
+                Gamepad(GamepadButtonType::South),
+            ],
+            Action::Back => vec![
+                Key(KeyCode::KeyB),
+                Key(KeyCode::Escape),
+                Gamepad(GamepadButtonType::East),
+            ],
+            Action::Retry => vec![
+                Key(KeyCode::KeyR),
+                Gamepad(GamepadButtonType::North),
+            ],
+            Action::Options => vec![
+                Key(KeyCode::KeyO),
+                Gamepad(GamepadButtonType::Select),
+            ],
+            Action::Mute => vec![
+                Key(KeyCode::KeyM),
+            ],
+            Action::Fullscreen => vec![
+                Key(KeyCode::F11),
+            ],
+            Action::Up => vec![
+                Key(KeyCode::ArrowUp),
+                Gamepad(GamepadButtonType::DPadUp),
+            ],
+            Action::Down => vec![
+                Key(KeyCode::ArrowDown),
+                Gamepad(GamepadButtonType::DPadDown),
+            ],
+            Action::Left => vec![
+                Key(KeyCode::ArrowLeft),
+                Gamepad(GamepadButtonType::DPadLeft),
+            ],
+            Action::Right => vec![
+                Key(KeyCode::ArrowRight),
+                Gamepad(GamepadButtonType::DPadRight),
+            ],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Binding {
+    Key(KeyCode),
+    // the left button also answers to touch taps
+    Mouse(MouseButton),
+    Gamepad(GamepadButtonType),
+}
+
+impl Binding {
+    pub fn label(&self) -> String {
+        match self {
+            Binding::Key(key) => {
+                let name = format!("{:?}", key);
+                let name = name.strip_prefix("Key").filter(|name| name.len() == 1).unwrap_or(&name);
+                format!("Key[{}]", name)
+            }
+            Binding::Mouse(button) => format!("Mouse[{:?}]", button),
+            Binding::Gamepad(button) => format!("Pad[{:?}]", button),
+        }
+    }
+
+    fn is_same_device(&self, other: &Binding) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+// Names the first keyboard or mouse binding, and the first gamepad button while a gamepad is connected
+pub fn hint(bindings: &[Binding], gamepad: bool) -> String {
+    let pointer = bindings.iter().find(|binding| !matches!(binding, Binding::Gamepad(_)));
+    let pad = bindings.iter().find(|binding| gamepad && matches!(binding, Binding::Gamepad(_)));
+    pointer.into_iter().chain(pad)
+        .map(Binding::label)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// Actions that are not listed fall back to their default bindings
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct InputBindings(HashMap<Action, Vec<Binding>>);
+
+impl InputBindings {
+    pub fn get(&self, action: Action) -> Vec<Binding> {
+        self.0.get(&action).cloned().unwrap_or_else(|| action.default_bindings())
+    }
+
+    // replaces the bindings of the same device, so a key never removes the gamepad button
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let mut bindings = self.get(action);
+        bindings.retain(|other| !other.is_same_device(&binding));
+        bindings.push(binding);
+        self.0.insert(action, bindings);
+    }
+
+    pub fn reset(&mut self, action: Action) {
+        self.0.remove(&action);
+    }
+}
+
+// Systems reading `ActionState` in PreUpdate run after this set
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionSystem;
+
+#[derive(Resource, Default, Debug)]
+pub struct ActionState {
+    presses: HashMap<Action, usize>,
+    timestamp: Duration,
+}
+
+impl ActionState {
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.presses(action) > 0
+    }
+
+    // How many times the action was pressed this frame.
+    // Two keys, two clicks or two fingers at once each count.
+    pub fn presses(&self, action: Action) -> usize {
+        self.presses.get(&action).copied().unwrap_or(0)
+    }
+
+    // Start of the frame that read this input, on the clock of `Time`.
+    // Winit gives its events no time, so an input is off by up to a frame,
+    // but a judgment made later still sees the cue as it was at that frame.
+    pub fn timestamp(&self) -> Duration {
+        self.timestamp
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update(
+    mut action_state: ResMut<ActionState>,
+    mut mouse_button_events: EventReader<MouseButtonInput>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    touches: Res<Touches>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+) {
+    action_state.timestamp = time.elapsed();
+
+    // `ButtonInput` folds a double click within one frame into a single press
+    let mouse_presses: Vec<MouseButton> = mouse_button_events.read()
+        .filter(|event| event.state == ButtonState::Pressed)
+        .map(|event| event.button)
+        .collect();
+    let presses = |binding: &Binding| match binding {
+        Binding::Key(key) => keyboard_input.just_pressed(*key) as usize,
+        Binding::Mouse(button) => {
+            let clicks = mouse_presses.iter().filter(|pressed| *pressed == button).count();
+            let taps = if *button == MouseButton::Left { touches.iter_just_pressed().count() } else { 0 };
+            clicks + taps
+        }
+        Binding::Gamepad(button_type) => gamepads.iter().filter(|gamepad| {
+            gamepad_buttons.just_pressed(GamepadButton::new(*gamepad, *button_type))
+        }).count(),
+    };
+
+    action_state.presses = ACTIONS
+        .into_iter()
+        .map(|action| (action, settings.bindings.get(action).iter().map(presses).sum()))
+        .filter(|(_, count)| *count > 0)
+        .collect();
+}
+
+pub struct InputPlugin;
+
+impl Plugin for InputPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ActionState>()
+            .add_systems(PreUpdate, update.in_set(ActionSystem).after(InputSystem))
+        ;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hint_names_one_binding_per_device() {
+        let bindings = Action::Confirm.default_bindings();
+        assert_eq!(hint(&bindings, false), "Key[Enter]");
+        assert_eq!(hint(&bindings, true), "Key[Enter]/Pad[South]");
+    }
+
+    #[test]
+    fn hint_follows_a_rebinding() {
+        let mut bindings = InputBindings::default();
+        bindings.rebind(Action::Back, Binding::Key(KeyCode::KeyQ));
+        assert_eq!(hint(&bindings.get(Action::Back), true), "Key[Q]/Pad[East]");
+    }
+}
@@ -0,0 +1,212 @@
+use bevy::prelude::*;
+
+use crate::{
+    MenuState,
+};
+use crate::input::{
+    ACTIONS,
+    Action,
+    Binding,
+};
+use crate::locale::{
+    HintKeys,
+    KeyHints,
+    Locale,
+    LocalizedText,
+};
+use crate::menu::overlay_bundle;
+use crate::settings::Settings;
+
+const KEYBINDS_SIZE: f32 = 24.0;
+const BACKGROUND_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const TEXT_SIZE: f32 = 12.0;
+const TEXT_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+const TEXT_PADDING: Val = Val::Px(3.0);
+const SELECTED_COLOR: Color = Color::srgb(0.8, 0.2, 0.2);
+
+// The screen is driven by fixed keys and buttons, so a bad binding can always be undone
+const NAVIGATION_UP: (KeyCode, GamepadButtonType) = (KeyCode::ArrowUp, GamepadButtonType::DPadUp);
+const NAVIGATION_DOWN: (KeyCode, GamepadButtonType) = (KeyCode::ArrowDown, GamepadButtonType::DPadDown);
+const NAVIGATION_REBIND: (KeyCode, GamepadButtonType) = (KeyCode::Enter, GamepadButtonType::South);
+const NAVIGATION_RESET: (KeyCode, GamepadButtonType) = (KeyCode::Backspace, GamepadButtonType::West);
+const NAVIGATION_BACK: (KeyCode, GamepadButtonType) = (KeyCode::Escape, GamepadButtonType::East);
+
+#[derive(Resource, Default)]
+struct Keybinds {
+    selected: usize,
+    waiting: bool,
+    captured: bool,
+}
+
+#[derive(Component)]
+struct KeybindsUi;
+
+#[derive(Component)]
+struct KeybindText(Action);
+
+fn setup(mut commands: Commands) {
+    println!("keybinds: setup");
+    commands.insert_resource(Keybinds::default());
+
+    let text_style = TextStyle {
+        font_size: TEXT_SIZE,
+        color: TEXT_COLOR,
+        ..Default::default()
+    };
+    let margin = Style {
+        margin: UiRect::all(TEXT_PADDING),
+        ..Default::default()
+    };
+
+    commands.spawn((
+        overlay_bundle(BACKGROUND_COLOR),
+        KeybindsUi,
+        StateScoped(MenuState::Keybinds),
+    ))
+    .insert(Name::new("keybinds"))
+    .with_children(|parent| {
+        // title
+        parent.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: KEYBINDS_SIZE,
+                    color: TEXT_COLOR,
+                    ..Default::default()
+                },
+            )
+            .with_style(margin.clone()),
+            LocalizedText("keybinds.title"),
+        ))
+        .insert(Name::new("title"));
+        // actions
+        for action in ACTIONS {
+            parent.spawn((
+                TextBundle::from_section("", text_style.clone())
+                    .with_style(margin.clone()),
+                KeybindText(action),
+            ))
+            .insert(Name::new(format!("{:?}", action)));
+        }
+        // help
+        parent.spawn((
+            TextBundle::from_section("", text_style.clone())
+                .with_style(margin),
+            KeyHints(&[
+                ("keybinds.help", HintKeys::Text),
+                ("keybinds.rebind", HintKeys::Fixed(NAVIGATION_REBIND.0, NAVIGATION_REBIND.1)),
+                ("keybinds.reset", HintKeys::Fixed(NAVIGATION_RESET.0, NAVIGATION_RESET.1)),
+                ("common.back", HintKeys::Fixed(NAVIGATION_BACK.0, NAVIGATION_BACK.1)),
+            ]),
+        ))
+        .insert(Name::new("help"));
+    });
+}
+
+fn capture(
+    mut keybinds: ResMut<Keybinds>,
+    mut settings: ResMut<Settings>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_events: Res<ButtonInput<MouseButton>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+) {
+    if !keybinds.waiting { return }
+
+    let binding = keyboard_input.get_just_pressed().next().map(|key| Binding::Key(*key))
+        .or_else(|| mouse_events.get_just_pressed().next().map(|button| Binding::Mouse(*button)))
+        .or_else(|| {
+            gamepad_buttons.get_just_pressed().next()
+                .map(|button| Binding::Gamepad(button.button_type))
+        });
+    let Some(binding) = binding else { return };
+
+    keybinds.waiting = false;
+    keybinds.captured = true;
+    if binding == Binding::Key(NAVIGATION_BACK.0) {
+        println!("keybinds: cancel");
+        return;
+    }
+
+    let action = ACTIONS[keybinds.selected];
+    println!("keybinds: bind {:?} to {:?}", binding, action);
+    settings.bindings.rebind(action, binding);
+}
+
+fn update(
+    mut keybinds: ResMut<Keybinds>,
+    mut settings: ResMut<Settings>,
+    mut next_state: ResMut<NextState<MenuState>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+) {
+    if keybinds.waiting { return }
+    // the input that was just bound must not also navigate
+    if keybinds.captured {
+        keybinds.captured = false;
+        return;
+    }
+
+    let just_pressed = |(key, button_type): (KeyCode, GamepadButtonType)| {
+        keyboard_input.just_pressed(key)
+            || gamepad_buttons.get_just_pressed().any(|button| button.button_type == button_type)
+    };
+
+    if just_pressed(NAVIGATION_UP) {
+        keybinds.selected = (keybinds.selected + ACTIONS.len() - 1) % ACTIONS.len();
+    }
+    else if just_pressed(NAVIGATION_DOWN) {
+        keybinds.selected = (keybinds.selected + 1) % ACTIONS.len();
+    }
+    else if just_pressed(NAVIGATION_REBIND) {
+        println!("keybinds: waiting for input");
+        keybinds.waiting = true;
+    }
+    else if just_pressed(NAVIGATION_RESET) {
+        let action = ACTIONS[keybinds.selected];
+        println!("keybinds: reset {:?}", action);
+        settings.bindings.reset(action);
+    }
+    else if just_pressed(NAVIGATION_BACK) {
+        println!("keybinds: moved state to Options from Keybinds");
+        next_state.set(MenuState::Options);
+    }
+}
+
+fn update_text(
+    mut query: Query<(&mut Text, &KeybindText)>,
+    keybinds: Res<Keybinds>,
+    settings: Res<Settings>,
+    locale: Locale,
+) {
+    if !keybinds.is_changed() && !settings.is_changed() && !locale.is_changed() { return }
+
+    for (mut text, keybind) in query.iter_mut() {
+        let is_selected = ACTIONS[keybinds.selected] == keybind.0;
+        let cursor = if is_selected { "> " } else { "  " };
+        let bindings = if is_selected && keybinds.waiting {
+            locale.get("keybinds.waiting")
+        } else {
+            settings.bindings.get(keybind.0).iter()
+                .map(Binding::label)
+                .collect::<Vec<_>>()
+                .join(" / ")
+        };
+        text.sections[0].value = format!("{}{}: {}", cursor, locale.get(keybind.0.text_key()), bindings);
+        text.sections[0].style.color = if is_selected { SELECTED_COLOR } else { TEXT_COLOR };
+    }
+}
+
+pub struct KeybindsPlugin;
+
+impl Plugin for KeybindsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(MenuState::Keybinds), setup)
+            .add_systems(Update, (
+                capture,
+                update,
+                update_text,
+            ).chain().run_if(in_state(MenuState::Keybinds)))
+        ;
+    }
+}
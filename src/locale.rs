@@ -0,0 +1,247 @@
+use bevy::{
+    prelude::*,
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    ecs::system::SystemParam,
+    utils::HashMap,
+};
+use thiserror::Error;
+
+use crate::{
+    PATH_LOCALE_ENGLISH,
+    PATH_LOCALE_JAPANESE,
+};
+use crate::input::{
+    self,
+    Action,
+    Binding,
+};
+use crate::settings::{
+    LANGUAGES,
+    Language,
+    Settings,
+};
+
+// Language shown when a text is missing from the selected table
+const FALLBACK_LANGUAGE: Language = Language::Japanese;
+
+// UI texts of one language, by key
+#[derive(Asset, TypePath, Debug)]
+pub struct StringTable(HashMap<String, String>);
+
+#[derive(Default)]
+struct StringTableLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+enum StringTableLoaderError {
+    #[error("Could not load asset: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for StringTableLoader {
+    type Asset = StringTable;
+    type Settings = ();
+    type Error = StringTableLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let texts = ron::de::from_bytes::<HashMap<String, String>>(&bytes)?;
+
+        Ok(StringTable(texts))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["locale.ron"]
+    }
+}
+
+fn path(language: Language) -> &'static str {
+    match language {
+        Language::Japanese => PATH_LOCALE_JAPANESE,
+        Language::English => PATH_LOCALE_ENGLISH,
+    }
+}
+
+// Changes whenever the texts on screen have to be written again
+#[derive(Resource)]
+pub struct Locales {
+    tables: HashMap<Language, Handle<StringTable>>,
+    language: Language,
+}
+
+impl FromWorld for Locales {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let tables = LANGUAGES.into_iter()
+            .map(|language| (language, asset_server.load(path(language))))
+            .collect();
+        let language = world.resource::<Settings>().language;
+
+        Self { tables, language }
+    }
+}
+
+// Looks up texts in the selected language
+#[derive(SystemParam)]
+pub struct Locale<'w> {
+    locales: Res<'w, Locales>,
+    tables: Res<'w, Assets<StringTable>>,
+}
+
+impl Locale<'_> {
+    pub fn get(&self, key: &str) -> String {
+        [self.locales.language, FALLBACK_LANGUAGE].into_iter()
+            .filter_map(|language| self.tables.get(&self.locales.tables[&language]))
+            .find_map(|table| table.0.get(key))
+            // a missing text still shows up, as its key
+            .map_or(key.to_string(), |text| text.clone())
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.locales.is_changed()
+    }
+}
+
+// A text whose first section is taken from the string tables
+#[derive(Component)]
+pub struct LocalizedText(pub &'static str);
+
+// What a part of a hint line names after its text
+#[derive(Clone, Copy, Debug)]
+pub enum HintKeys {
+    // only the text, for directions drawn as arrows
+    Text,
+    // whatever the player bound to the action
+    Action(Action),
+    // keys a screen answers to whatever is bound
+    Fixed(KeyCode, GamepadButtonType),
+}
+
+// A line of "text: keys" parts, written again when the bindings, the gamepads or the language change
+#[derive(Component)]
+pub struct KeyHints(pub &'static [(&'static str, HintKeys)]);
+
+fn sync(
+    mut locales: ResMut<Locales>,
+    mut events: EventReader<AssetEvent<StringTable>>,
+    settings: Res<Settings>,
+) {
+    // tables finish loading after the first screen is up, and may be reloaded
+    let loaded = events.read().count() > 0;
+
+    if locales.language != settings.language {
+        println!("locale: language {:?}", settings.language);
+        locales.language = settings.language;
+    } else if loaded {
+        locales.set_changed();
+    }
+}
+
+fn update_text(
+    mut query: Query<(&mut Text, Ref<LocalizedText>)>,
+    locale: Locale,
+) {
+    for (mut text, localized) in query.iter_mut() {
+        if !locale.is_changed() && !localized.is_changed() { continue }
+
+        text.sections[0].value = locale.get(localized.0);
+    }
+}
+
+fn update_hints(
+    mut query: Query<(&mut Text, Ref<KeyHints>)>,
+    settings: Res<Settings>,
+    gamepads: Res<Gamepads>,
+    locale: Locale,
+) {
+    let refresh = locale.is_changed() || settings.is_changed() || gamepads.is_changed();
+    let gamepad = gamepads.iter().next().is_some();
+
+    for (mut text, hints) in query.iter_mut() {
+        if !refresh && !hints.is_changed() { continue }
+
+        text.sections[0].value = hints.0.iter()
+            .map(|(key, keys)| {
+                let bindings = match keys {
+                    HintKeys::Text => return locale.get(key),
+                    HintKeys::Action(action) => settings.bindings.get(*action),
+                    HintKeys::Fixed(key, button) => vec![Binding::Key(*key), Binding::Gamepad(*button)],
+                };
+                format!("{}: {}", locale.get(key), input::hint(&bindings, gamepad))
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+    }
+}
+
+pub struct LocalePlugin;
+
+impl Plugin for LocalePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<StringTable>()
+            .init_asset_loader::<StringTableLoader>()
+            .init_resource::<Locales>()
+            .add_systems(PreUpdate, sync)
+            .add_systems(Update, (update_text, update_hints))
+        ;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    fn table(texts: &[(&str, &str)]) -> StringTable {
+        StringTable(texts.iter().map(|(key, text)| (key.to_string(), text.to_string())).collect())
+    }
+
+    // `English` is left without a table when `english` is `None`, as while it loads
+    fn locale_world(language: Language, english: Option<StringTable>) -> World {
+        let mut world = World::new();
+        let mut tables = Assets::<StringTable>::default();
+        let japanese = tables.add(table(&[("greeting", "こんにちは"), ("farewell", "さようなら")]));
+        let english = english.map_or(Handle::default(), |english| tables.add(english));
+        world.insert_resource(tables);
+        world.insert_resource(Locales {
+            tables: HashMap::from([(Language::Japanese, japanese), (Language::English, english)]),
+            language,
+        });
+        world
+    }
+
+    fn get(world: &mut World, key: &'static str) -> String {
+        world.run_system_once(move |locale: Locale| locale.get(key))
+    }
+
+    #[test]
+    fn get_reads_the_selected_language() {
+        let mut world = locale_world(Language::English, Some(table(&[("greeting", "Hello")])));
+        assert_eq!(get(&mut world, "greeting"), "Hello");
+        let mut world = locale_world(Language::Japanese, Some(table(&[("greeting", "Hello")])));
+        assert_eq!(get(&mut world, "greeting"), "こんにちは");
+    }
+
+    #[test]
+    fn get_falls_back_to_japanese_then_the_key() {
+        let mut world = locale_world(Language::English, Some(table(&[("greeting", "Hello")])));
+        assert_eq!(get(&mut world, "farewell"), "さようなら");
+        assert_eq!(get(&mut world, "missing.key"), "missing.key");
+    }
+
+    #[test]
+    fn get_falls_back_while_a_table_loads() {
+        let mut world = locale_world(Language::English, None);
+        assert_eq!(get(&mut world, "greeting"), "こんにちは");
+    }
+}
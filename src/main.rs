@@ -1,31 +1,65 @@
 use bevy::{
     prelude::*,
     asset::AssetMetaCheck,
+    window::WindowResolution,
 };
-// use bevy_hanabi::prelude::*;
 
+use crate::audio::{
+    AudioBus,
+    SoundPlayer,
+};
+use crate::input::{
+    Action,
+    ActionState,
+};
+
+mod animation;
+mod audio;
+mod calibration;
+mod credits;
+mod fonts;
 mod mainmenu;
 mod ingame;
-mod gameover;
-mod gameclear;
+mod gamepad;
+mod highscores;
+mod input;
+mod keybinds;
+mod locale;
+mod menu;
+mod options;
+mod pointer;
+mod result;
+mod settings;
+mod spritebutton;
+mod storage;
+mod transition;
+mod viewport;
 
 const GAMETITLE: &str = "いっとくタイミングゲーム";
+// size of the virtual screen, the window shows it scaled and letterboxed
 const WINDOW_SIZE: Vec2 = Vec2::new(640.0, 480.0);
 const CURSOR_RANGE: f32 = 10.0;
 const PATH_IMAGE_CHARACTER: &str = "ittoku-timing-game/character.png";
+const PATH_ANIMATION_CHARACTER: &str = "ittoku-timing-game/character.anim.ron";
 const PATH_IMAGE_BAR: &str = "ittoku-timing-game/bar.png";
 const PATH_IMAGE_CUE: &str = "ittoku-timing-game/cue.png";
 const PATH_IMAGE_INGAME: &str = "ittoku-timing-game/ingame.png";
 const PATH_IMAGE_MAINMENU: &str = "ittoku-timing-game/mainmenu.png";
 const PATH_IMAGE_TIMINGBUTTON: &str = "ittoku-timing-game/timingbutton.png";
+const PATH_ANIMATION_TIMINGBUTTON: &str = "ittoku-timing-game/timingbutton.anim.ron";
 const PATH_SOUND_PERFECT: &str = "ittoku-timing-game/perfect.ogg";
 const PATH_SOUND_GOOD: &str = "ittoku-timing-game/good.ogg";
 const PATH_SOUND_OK: &str = "ittoku-timing-game/ok.ogg";
 
 const PATH_FONT: &str = "fonts/misaki_gothic.ttf";
 const PATH_IMAGE_PAUSEBUTTON: &str = "images/pausebutton-light.png";
+const PATH_ANIMATION_PAUSEBUTTON: &str = "images/pausebutton-light.anim.ron";
 const PATH_SOUND_CLICK: &str = "sounds/click.ogg";
 const PATH_SOUND_REVERSAL: &str = "sounds/reversal.ogg";
+const PATH_SETTINGS: &str = "settings.ron";
+const PATH_HIGHSCORES: &str = "highscores.ron";
+const PATH_LOCALE_JAPANESE: &str = "locales/ja.locale.ron";
+const PATH_LOCALE_ENGLISH: &str = "locales/en.locale.ron";
 
 const BACKGROUND_COLOR: Color = Color::srgb(0.25, 0.25, 0.25);
 
@@ -34,14 +68,23 @@ pub enum AppState {
     #[default]
     Mainmenu,
     Ingame,
-    Pause,
     Gameover,
     Gameclear,
+    // passed through on the way back into `Ingame`, so a restarted round is set up anew
+    Restart,
 }
 
-#[derive(Resource, Deref, DerefMut, Debug)]
-struct Config {
-    setup_ingame: bool,
+// Screens opened on top of the main menu or a paused round, which stays underneath
+#[derive(SubStates, Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[source(AppState = AppState::Mainmenu | AppState::Ingame)]
+pub enum MenuState {
+    #[default]
+    Closed,
+    Options,
+    Calibration,
+    Keybinds,
+    HighScores,
+    Credits,
 }
 
 #[derive(Resource, Deref)]
@@ -51,12 +94,16 @@ struct ClickSound(Handle<AudioSource>);
 pub struct Score(pub usize);
 
 fn main() {
+    let settings = settings::Settings::load();
+
     App::new()
         .add_plugins(DefaultPlugins
             .set(WindowPlugin {
                 primary_window: Some(Window {
-                    resolution: WINDOW_SIZE.into(),
+                    resolution: WindowResolution::from(WINDOW_SIZE)
+                        .with_scale_factor_override(settings.window_scale),
                     title: GAMETITLE.to_string(),
+                    resizable: true,
                     ..default()
                 }),
                 ..default()
@@ -68,19 +115,34 @@ fn main() {
             })
         )
         .init_state::<AppState>()
+        .add_sub_state::<MenuState>()
+        .enable_state_scoped_entities::<AppState>()
+        .enable_state_scoped_entities::<MenuState>()
         .insert_resource(ClearColor(BACKGROUND_COLOR))
-        .insert_resource(Time::<Fixed>::from_seconds(1.0 / 60.0))
-        .insert_resource(Config { setup_ingame: true })
         .insert_resource(Score(0))
-        // // Hanabi setup
-        // .add_plugins(HanabiPlugin)
+        .insert_resource(settings)
         // Plugins
         .add_systems(Startup, setup)
         .add_systems(Update, update)
+        .add_plugins(animation::AnimationPlugin)
+        .add_plugins(audio::AudioBusPlugin)
+        .add_plugins(gamepad::GamepadPlugin)
+        .add_plugins(input::InputPlugin)
+        .add_plugins(locale::LocalePlugin)
+        .add_plugins(fonts::FontsPlugin)
         .add_plugins(mainmenu::MainmenuPlugin)
         .add_plugins(ingame::IngamePlugin)
-        .add_plugins(gameover::GameoverPlugin)
-        .add_plugins(gameclear::GameclearPlugin)
+        .add_plugins(result::ResultPlugin)
+        .add_plugins(options::OptionsPlugin)
+        .add_plugins(calibration::CalibrationPlugin)
+        .add_plugins(keybinds::KeybindsPlugin)
+        .add_plugins(menu::MenuPlugin)
+        .add_plugins(highscores::HighScoresPlugin)
+        .add_plugins(credits::CreditsPlugin)
+        .add_plugins(settings::SettingsPlugin)
+        .add_plugins(spritebutton::SpriteButtonPlugin)
+        .add_plugins(transition::TransitionPlugin)
+        .add_plugins(viewport::ViewportPlugin)
         .run();
 }
 
@@ -89,22 +151,17 @@ fn setup(
     asset_server: Res<AssetServer>,
 ) {
     println!("main: setup");
-    // camera
-    commands.spawn(Camera2dBundle::default());
     // click sound
     let click_sound = asset_server.load(PATH_SOUND_CLICK);
     commands.insert_resource(ClickSound(click_sound));
 }
 
 fn update(
-    mut commands: Commands,
-    mouse_events: Res<ButtonInput<MouseButton>>,
+    mut sound_player: SoundPlayer,
+    action_state: Res<ActionState>,
     sound: Res<ClickSound>,
 ) {
-    if !mouse_events.just_pressed(MouseButton::Left) { return }
+    if !action_state.just_pressed(Action::Confirm) { return }
     println!("main: play click sound");
-    commands.spawn(AudioBundle {
-        source: sound.clone(),
-        settings: PlaybackSettings::DESPAWN
-    });
+    sound_player.play(sound.clone(), AudioBus::Sfx);
 }
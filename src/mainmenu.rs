@@ -1,87 +1,166 @@
-use bevy::{
-    prelude::*,
-    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
-};
+use bevy::prelude::*;
 
 use crate::{
-    GAMETITLE,
-    WINDOW_SIZE,
-    PATH_FONT,
     PATH_IMAGE_MAINMENU,
     AppState,
-    Config,
+    MenuState,
+};
+use crate::locale::{
+    Locale,
+    LocalizedText,
+};
+use crate::menu::{
+    button_bundle,
+    MenuButton,
+    MenuPressed,
 };
+use crate::settings::Settings;
 
 const GAMETITLE_SIZE: f32 = 24.0;
 const GAMETITLE_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
-const CLICKSTART_TEXT: &str = "クリックしてスタート";
-const CLICKSTART_COLOR: Color = Color::srgb(0.2, 0.2, 0.2);
-const BOARD_SIZE: Vec2 = Vec2::new(320.0, 240.0);
 const BOARD_COLOR: Color = Color::srgba(0.9, 0.9, 0.9, 0.75);
-const TEXT_SIZE: f32 = 20.0;
+const BOARD_PADDING: Val = Val::Px(16.0);
+const TEXT_SIZE: f32 = 16.0;
+const TEXT_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+const TEXT_PADDING: Val = Val::Px(8.0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MainmenuItem {
+    Start,
+    Mode,
+    HighScores,
+    Options,
+    Credits,
+    Quit,
+}
+
+// there is no process to quit in a browser tab
+#[cfg(not(target_arch = "wasm32"))]
+const ITEMS: [MainmenuItem; 6] = [
+    MainmenuItem::Start,
+    MainmenuItem::Mode,
+    MainmenuItem::HighScores,
+    MainmenuItem::Options,
+    MainmenuItem::Credits,
+    MainmenuItem::Quit,
+];
+
+#[cfg(target_arch = "wasm32")]
+const ITEMS: [MainmenuItem; 5] = [
+    MainmenuItem::Start,
+    MainmenuItem::Mode,
+    MainmenuItem::HighScores,
+    MainmenuItem::Options,
+    MainmenuItem::Credits,
+];
+
+impl MainmenuItem {
+    fn text_key(&self) -> &'static str {
+        match self {
+            MainmenuItem::Start => "mainmenu.start",
+            MainmenuItem::Mode => "mainmenu.mode",
+            MainmenuItem::HighScores => "mainmenu.highscores",
+            MainmenuItem::Options => "mainmenu.options",
+            MainmenuItem::Credits => "mainmenu.credits",
+            MainmenuItem::Quit => "mainmenu.quit",
+        }
+    }
+
+    fn label(&self, locale: &Locale, settings: &Settings) -> String {
+        match self {
+            MainmenuItem::Mode => {
+                format!("{}{}", locale.get(self.text_key()), locale.get(settings.difficulty.text_key()))
+            }
+            _ => locale.get(self.text_key()),
+        }
+    }
+}
 
 #[derive(Component)]
 struct Mainmenu;
 
+#[derive(Component)]
+struct MainmenuButton(MainmenuItem);
+
+#[derive(Component)]
+struct MainmenuText(MainmenuItem);
+
 fn setup(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
     asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+    locale: Locale,
 ) {
     println!("mainmenu: setup");
-    // game title
-    let top = Val::Px(WINDOW_SIZE.y / 2.0 - GAMETITLE_SIZE / 2.0 - BOARD_SIZE.y / 4.0);
-
     commands.spawn((
-        TextBundle::from_section(
-            GAMETITLE,
-            TextStyle {
-                font: asset_server.load(PATH_FONT),
-                font_size: GAMETITLE_SIZE,
-                color: GAMETITLE_COLOR,
-            }
-        )
-        .with_style(Style {
-            position_type: PositionType::Relative,
-            justify_self: JustifySelf::Center,
-            top,
-            ..Default::default()
-        }),
-        Mainmenu,
-    ))
-    .insert(Name::new("gametitle"));
-    // click start
-    let top = Val::Px(WINDOW_SIZE.y / 2.0 - TEXT_SIZE / 2.0 + BOARD_SIZE.y / 4.0);
-
-    commands.spawn((
-        TextBundle::from_section(
-            CLICKSTART_TEXT,
-            TextStyle {
-                font: asset_server.load(PATH_FONT),
-                font_size: TEXT_SIZE,
-                color: CLICKSTART_COLOR,
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
             },
-        )
-        .with_style(Style {
-            position_type: PositionType::Relative,
-            justify_self: JustifySelf::Center,
-            top,
-            ..Default::default()
-        }),
-        Mainmenu,
-    ))
-    .insert(Name::new("clickstart"));
-    // board
-    commands.spawn((
-        MaterialMesh2dBundle {
-            mesh: Mesh2dHandle(meshes.add(Rectangle::new(BOARD_SIZE.x, BOARD_SIZE.y))),
-            material: materials.add(BOARD_COLOR),
             ..Default::default()
         },
         Mainmenu,
+        StateScoped(AppState::Mainmenu),
     ))
-    .insert(Name::new("board"));
+    .insert(Name::new("mainmenu"))
+    .with_children(|parent| {
+        parent.spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(BOARD_PADDING),
+                ..Default::default()
+            },
+            background_color: BOARD_COLOR.into(),
+            ..Default::default()
+        })
+        .insert(Name::new("board"))
+        .with_children(|parent| {
+            // game title
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: GAMETITLE_SIZE,
+                        color: GAMETITLE_COLOR,
+                        ..Default::default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(TEXT_PADDING),
+                    ..Default::default()
+                }),
+                LocalizedText("mainmenu.title"),
+            ))
+            .insert(Name::new("gametitle"));
+            // buttons
+            for (index, item) in ITEMS.into_iter().enumerate() {
+                parent.spawn((
+                    button_bundle(),
+                    MenuButton(index),
+                    MainmenuButton(item),
+                ))
+                .insert(Name::new(format!("{:?}", item)))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            item.label(&locale, &settings),
+                            TextStyle {
+                                font_size: TEXT_SIZE,
+                                color: TEXT_COLOR,
+                                ..Default::default()
+                            },
+                        ),
+                        MainmenuText(item),
+                    ));
+                });
+            }
+        });
+    });
     // image
     commands.spawn((
         SpriteBundle {
@@ -90,25 +169,61 @@ fn setup(
             ..Default::default()
         },
         Mainmenu,
+        StateScoped(AppState::Mainmenu),
     ))
     .insert(Name::new("image"));
 }
 
 fn update(
-    mut commands: Commands,
-    mut next_state: ResMut<NextState<AppState>>,
-    mut config: ResMut<Config>, 
-    mouse_events: Res<ButtonInput<MouseButton>>,
-    query: Query<Entity, With<Mainmenu>>,
+    mut events: EventReader<MenuPressed>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut next_menu_state: ResMut<NextState<MenuState>>,
+    mut app_exit_events: EventWriter<AppExit>,
+    mut settings: ResMut<Settings>,
+    query: Query<&MainmenuButton>,
+) {
+    for event in events.read() {
+        let Ok(button) = query.get(event.0) else { continue };
+
+        match button.0 {
+            MainmenuItem::Start => {
+                println!("mainmenu: moved state to Ingame from Mainmenu");
+                next_app_state.set(AppState::Ingame);
+            }
+            MainmenuItem::Mode => {
+                settings.difficulty = settings.difficulty.next();
+                println!("mainmenu: difficulty {:?}", settings.difficulty);
+            }
+            MainmenuItem::HighScores => {
+                println!("mainmenu: moved state to HighScores from Closed");
+                next_menu_state.set(MenuState::HighScores);
+            }
+            MainmenuItem::Options => {
+                println!("mainmenu: moved state to Options from Closed");
+                next_menu_state.set(MenuState::Options);
+            }
+            MainmenuItem::Credits => {
+                println!("mainmenu: moved state to Credits from Closed");
+                next_menu_state.set(MenuState::Credits);
+            }
+            MainmenuItem::Quit => {
+                println!("mainmenu: quit");
+                app_exit_events.send(AppExit::Success);
+            }
+        }
+    }
+}
+
+fn update_text(
+    mut query: Query<(&mut Text, &MainmenuText)>,
+    settings: Res<Settings>,
+    locale: Locale,
 ) {
-    if !mouse_events.just_pressed(MouseButton::Left) { return }
-
-    println!("mainmenu: config setup ingame is true");
-    config.setup_ingame = true;
-    println!("mainmenu: despawn");
-    for entity in query.iter() { commands.entity(entity).despawn() }
-    println!("mainmenu: moved state to Ingame from Mainmeu");
-    next_state.set(AppState::Ingame);
+    if !settings.is_changed() && !locale.is_changed() { return }
+
+    for (mut text, item) in query.iter_mut() {
+        text.sections[0].value = item.0.label(&locale, &settings);
+    }
 }
 
 pub struct MainmenuPlugin;
@@ -117,7 +232,10 @@ impl Plugin for MainmenuPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_systems(OnEnter(AppState::Mainmenu), setup)
-            .add_systems(Update, update.run_if(in_state(AppState::Mainmenu)))
+            .add_systems(Update, (
+                update,
+                update_text,
+            ).chain().run_if(in_state(AppState::Mainmenu)))
         ;
     }
 }
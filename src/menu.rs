@@ -0,0 +1,163 @@
+use bevy::{
+    prelude::*,
+    ui::FocusPolicy,
+};
+
+use crate::MenuState;
+use crate::input::{
+    Action,
+    ActionState,
+};
+
+const BUTTON_SIZE: Vec2 = Vec2::new(200.0, 28.0);
+const BUTTON_MARGIN: Val = Val::Px(4.0);
+const BUTTON_COLOR: Color = Color::srgb(0.8, 0.8, 0.8);
+const FOCUSED_COLOR: Color = Color::srgb(0.95, 0.75, 0.75);
+const PRESSED_COLOR: Color = Color::srgb(0.8, 0.2, 0.2);
+const BORDER_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+const FOCUSED_BORDER_COLOR: Color = Color::srgb(0.8, 0.2, 0.2);
+
+// A button of a vertical menu, ordered by its index for Up/Down
+#[derive(Component)]
+pub struct MenuButton(pub usize);
+
+// The button that Confirm presses, moved by Up/Down or by hovering
+#[derive(Component)]
+pub struct Focused;
+
+// Sent when a menu button is clicked, tapped or confirmed
+#[derive(Event)]
+pub struct MenuPressed(pub Entity);
+
+pub fn button_bundle() -> ButtonBundle {
+    ButtonBundle {
+        style: Style {
+            width: Val::Px(BUTTON_SIZE.x),
+            height: Val::Px(BUTTON_SIZE.y),
+            margin: UiRect::all(BUTTON_MARGIN),
+            border: UiRect::all(Val::Px(2.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        background_color: BUTTON_COLOR.into(),
+        border_color: BORDER_COLOR.into(),
+        ..Default::default()
+    }
+}
+
+// Root of a screen opened over a menu, filling the window with its items centered in a column
+pub fn overlay_bundle(background_color: Color) -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        background_color: background_color.into(),
+        // the menu below must not be clicked through this screen
+        focus_policy: FocusPolicy::Block,
+        ..Default::default()
+    }
+}
+
+// Menus below an open options screen must not react to its input
+fn is_on_top(menu_state: Option<Res<State<MenuState>>>) -> bool {
+    menu_state.is_none_or(|state| *state.get() == MenuState::Closed)
+}
+
+fn navigate(
+    mut commands: Commands,
+    query: Query<(Entity, &MenuButton, Ref<Interaction>, Has<Focused>)>,
+    action_state: Res<ActionState>,
+) {
+    if query.is_empty() { return }
+
+    let mut buttons: Vec<_> = query.iter().collect();
+    buttons.sort_by_key(|(_, button, _, _)| button.0);
+    let focused = buttons.iter().position(|(_, _, _, is_focused)| *is_focused);
+
+    // a pointer resting on a button must not keep taking the focus from the keys
+    let hovered = buttons.iter().position(|(_, _, interaction, _)| {
+        interaction.is_changed() && **interaction != Interaction::None
+    });
+    let next = if let Some(hovered) = hovered {
+        hovered
+    } else if let Some(focused) = focused {
+        if action_state.just_pressed(Action::Up) {
+            (focused + buttons.len() - 1) % buttons.len()
+        } else if action_state.just_pressed(Action::Down) {
+            (focused + 1) % buttons.len()
+        } else {
+            focused
+        }
+    } else {
+        0
+    };
+    if focused == Some(next) { return }
+
+    if let Some(focused) = focused {
+        commands.entity(buttons[focused].0).remove::<Focused>();
+    }
+    commands.entity(buttons[next].0).insert(Focused);
+}
+
+#[allow(clippy::type_complexity)]
+fn press(
+    mut events: EventWriter<MenuPressed>,
+    query: Query<(Entity, &Interaction), (With<MenuButton>, Changed<Interaction>)>,
+    focused_query: Query<Entity, (With<MenuButton>, With<Focused>)>,
+    mouse_events: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    action_state: Res<ActionState>,
+) {
+    for (entity, interaction) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            events.send(MenuPressed(entity));
+        }
+    }
+
+    // a click also counts as Confirm, but only the button under the pointer should answer it
+    let pointer_pressed = mouse_events.just_pressed(MouseButton::Left) || touches.any_just_pressed();
+    if !action_state.just_pressed(Action::Confirm) || pointer_pressed { return }
+    let Ok(entity) = focused_query.get_single() else { return };
+    events.send(MenuPressed(entity));
+}
+
+fn update_style(
+    mut query: Query<
+        (&Interaction, Has<Focused>, &mut BackgroundColor, &mut BorderColor),
+        With<MenuButton>,
+    >,
+) {
+    for (interaction, is_focused, mut color, mut border_color) in query.iter_mut() {
+        let background = match (interaction, is_focused) {
+            (Interaction::Pressed, _) => PRESSED_COLOR,
+            (_, true) => FOCUSED_COLOR,
+            _ => BUTTON_COLOR,
+        };
+        let border = if is_focused { FOCUSED_BORDER_COLOR } else { BORDER_COLOR };
+
+        // written only on a change, so the UI is not laid out again every frame
+        if color.0 != background { color.0 = background }
+        if border_color.0 != border { border_color.0 = border }
+    }
+}
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<MenuPressed>()
+            .add_systems(Update, (
+                navigate,
+                press,
+            ).chain().run_if(is_on_top))
+            .add_systems(Update, update_style)
+        ;
+    }
+}
@@ -0,0 +1,351 @@
+use bevy::prelude::*;
+
+use crate::{
+    AppState,
+    MenuState,
+};
+use crate::input::{
+    self,
+    Action,
+    ActionState,
+};
+use crate::ingame::IngameState;
+use crate::locale::{
+    HintKeys,
+    KeyHints,
+    Locale,
+    LocalizedText,
+};
+use crate::menu::overlay_bundle;
+use crate::settings::{
+    HIT_COOLDOWN_LIMIT,
+    JUDGMENT_OFFSET_LIMIT,
+    WINDOW_SCALE_RANGE,
+    Settings,
+};
+
+const OPTIONS_SIZE: f32 = 24.0;
+const BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+const BOARD_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const BOARD_PADDING: Val = Val::Px(20.0);
+const TEXT_SIZE: f32 = 16.0;
+const TEXT_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+const TEXT_PADDING: Val = Val::Px(6.0);
+// tighter between items, so all of them fit on the screen
+const ITEM_SPACING: Val = Val::Px(3.0);
+const SELECTED_COLOR: Color = Color::srgb(0.8, 0.2, 0.2);
+const VOLUME_STEP: f32 = 0.1;
+const OFFSET_STEP: f32 = 0.005;
+const COOLDOWN_STEP: f32 = 0.05;
+const WINDOW_SCALE_STEP: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum OptionItem {
+    MasterVolume,
+    SfxVolume,
+    MusicVolume,
+    Mute,
+    AutoPause,
+    JudgmentOffset,
+    Calibrate,
+    HitCooldown,
+    OneHitPerPass,
+    Keybinds,
+    Difficulty,
+    Language,
+    WindowScale,
+    Fullscreen,
+    ScreenShake,
+    HitStop,
+    ZoomPulse,
+}
+
+const ITEMS: [OptionItem; 17] = [
+    OptionItem::MasterVolume,
+    OptionItem::SfxVolume,
+    OptionItem::MusicVolume,
+    OptionItem::Mute,
+    OptionItem::AutoPause,
+    OptionItem::JudgmentOffset,
+    OptionItem::Calibrate,
+    OptionItem::HitCooldown,
+    OptionItem::OneHitPerPass,
+    OptionItem::Keybinds,
+    OptionItem::Difficulty,
+    OptionItem::Language,
+    OptionItem::WindowScale,
+    OptionItem::Fullscreen,
+    OptionItem::ScreenShake,
+    OptionItem::HitStop,
+    OptionItem::ZoomPulse,
+];
+
+impl OptionItem {
+    fn text_key(&self) -> &'static str {
+        match self {
+            OptionItem::MasterVolume => "options.master_volume",
+            OptionItem::SfxVolume => "options.sfx_volume",
+            OptionItem::MusicVolume => "options.music_volume",
+            OptionItem::Mute => "options.mute",
+            OptionItem::AutoPause => "options.auto_pause",
+            OptionItem::JudgmentOffset => "options.judgment_offset",
+            OptionItem::Calibrate => "options.calibrate",
+            OptionItem::HitCooldown => "options.hit_cooldown",
+            OptionItem::OneHitPerPass => "options.one_hit_per_pass",
+            OptionItem::Keybinds => "options.keybinds",
+            OptionItem::Difficulty => "options.difficulty",
+            OptionItem::Language => "options.language",
+            OptionItem::WindowScale => "options.window_scale",
+            OptionItem::Fullscreen => "options.fullscreen",
+            OptionItem::ScreenShake => "options.screen_shake",
+            OptionItem::HitStop => "options.hit_stop",
+            OptionItem::ZoomPulse => "options.zoom_pulse",
+        }
+    }
+
+    fn value(&self, locale: &Locale, settings: &Settings, gamepad: bool) -> String {
+        let toggle = |on: bool| locale.get(if on { "common.on" } else { "common.off" });
+
+        match self {
+            OptionItem::MasterVolume => percent(settings.master_volume),
+            OptionItem::SfxVolume => percent(settings.sfx_volume),
+            OptionItem::MusicVolume => percent(settings.music_volume),
+            OptionItem::Mute => toggle(settings.muted),
+            OptionItem::AutoPause => toggle(settings.auto_pause),
+            OptionItem::JudgmentOffset => {
+                format!("{:+}ms", (settings.judgment_offset * 1000.0).round())
+            }
+            OptionItem::HitCooldown => if settings.hit_cooldown > 0.0 {
+                format!("{}ms", (settings.hit_cooldown * 1000.0).round())
+            } else {
+                locale.get("options.hit_cooldown_none")
+            },
+            OptionItem::OneHitPerPass => toggle(settings.one_hit_per_pass),
+            OptionItem::Calibrate | OptionItem::Keybinds => {
+                input::hint(&settings.bindings.get(Action::Confirm), gamepad)
+            }
+            OptionItem::Difficulty => locale.get(settings.difficulty.text_key()),
+            OptionItem::Language => settings.language.label().to_string(),
+            OptionItem::WindowScale => format!("x{}", settings.window_scale),
+            OptionItem::Fullscreen => toggle(settings.fullscreen),
+            OptionItem::ScreenShake => toggle(settings.screen_shake),
+            OptionItem::HitStop => toggle(settings.hit_stop),
+            OptionItem::ZoomPulse => toggle(settings.zoom_pulse),
+        }
+    }
+
+    fn adjust(&self, settings: &mut Settings, direction: f32) {
+        let volume = |value: f32| (value + VOLUME_STEP * direction).clamp(0.0, 1.0);
+
+        match self {
+            OptionItem::MasterVolume => settings.master_volume = volume(settings.master_volume),
+            OptionItem::SfxVolume => settings.sfx_volume = volume(settings.sfx_volume),
+            OptionItem::MusicVolume => settings.music_volume = volume(settings.music_volume),
+            OptionItem::Mute => settings.muted = !settings.muted,
+            OptionItem::AutoPause => settings.auto_pause = !settings.auto_pause,
+            OptionItem::JudgmentOffset => {
+                settings.judgment_offset = (settings.judgment_offset + OFFSET_STEP * direction)
+                    .clamp(-JUDGMENT_OFFSET_LIMIT, JUDGMENT_OFFSET_LIMIT);
+            }
+            OptionItem::HitCooldown => {
+                settings.hit_cooldown = (settings.hit_cooldown + COOLDOWN_STEP * direction)
+                    .clamp(0.0, HIT_COOLDOWN_LIMIT);
+            }
+            OptionItem::OneHitPerPass => settings.one_hit_per_pass = !settings.one_hit_per_pass,
+            OptionItem::Calibrate | OptionItem::Keybinds => {},
+            OptionItem::Difficulty => {
+                settings.difficulty = if direction > 0.0 {
+                    settings.difficulty.next()
+                } else {
+                    settings.difficulty.prev()
+                };
+            }
+            OptionItem::Language => settings.language = settings.language.next(),
+            OptionItem::WindowScale => {
+                settings.window_scale = (settings.window_scale + WINDOW_SCALE_STEP * direction)
+                    .clamp(WINDOW_SCALE_RANGE.0, WINDOW_SCALE_RANGE.1);
+            }
+            OptionItem::Fullscreen => settings.fullscreen = !settings.fullscreen,
+            OptionItem::ScreenShake => settings.screen_shake = !settings.screen_shake,
+            OptionItem::HitStop => settings.hit_stop = !settings.hit_stop,
+            OptionItem::ZoomPulse => settings.zoom_pulse = !settings.zoom_pulse,
+        }
+    }
+}
+
+fn percent(value: f32) -> String {
+    format!("{}%", (value * 100.0).round())
+}
+
+#[derive(Resource, Deref, DerefMut, Default)]
+struct Selected(usize);
+
+#[derive(Component)]
+struct Options;
+
+#[derive(Component)]
+struct OptionText(OptionItem);
+
+fn open(
+    mut next_state: ResMut<NextState<MenuState>>,
+    action_state: Res<ActionState>,
+) {
+    if !action_state.just_pressed(Action::Options) { return }
+
+    println!("options: moved state to Options from Closed");
+    next_state.set(MenuState::Options);
+}
+
+fn setup(mut commands: Commands) {
+    println!("options: setup");
+    commands.insert_resource(Selected::default());
+
+    let text_style = |color: Color| TextStyle {
+        font_size: TEXT_SIZE,
+        color,
+        ..Default::default()
+    };
+
+    commands.spawn((
+        overlay_bundle(BACKGROUND_COLOR),
+        Options,
+        StateScoped(MenuState::Options),
+    ))
+    .insert(Name::new("options"))
+    .with_children(|parent| {
+        parent.spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(BOARD_PADDING),
+                ..Default::default()
+            },
+            background_color: BOARD_COLOR.into(),
+            ..Default::default()
+        })
+        .insert(Name::new("board"))
+        .with_children(|parent| {
+            // title
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: OPTIONS_SIZE,
+                        color: TEXT_COLOR,
+                        ..Default::default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(TEXT_PADDING),
+                    ..Default::default()
+                }),
+                LocalizedText("options.title"),
+            ))
+            .insert(Name::new("title"));
+            // items
+            for item in ITEMS {
+                parent.spawn((
+                    TextBundle::from_section("", text_style(TEXT_COLOR))
+                        .with_style(Style {
+                            margin: UiRect::axes(TEXT_PADDING, ITEM_SPACING),
+                            ..Default::default()
+                        }),
+                    OptionText(item),
+                ))
+                .insert(Name::new(format!("{:?}", item)));
+            }
+            // help
+            parent.spawn((
+                TextBundle::from_section("", text_style(TEXT_COLOR))
+                    .with_style(Style {
+                        margin: UiRect::top(TEXT_PADDING),
+                        ..Default::default()
+                    }),
+                KeyHints(&[
+                ("options.help", HintKeys::Text),
+                ("common.back", HintKeys::Action(Action::Back)),
+            ]),
+            ))
+            .insert(Name::new("help"));
+        });
+    });
+}
+
+fn update(
+    mut settings: ResMut<Settings>,
+    mut selected: ResMut<Selected>,
+    mut next_state: ResMut<NextState<MenuState>>,
+    action_state: Res<ActionState>,
+) {
+    let item = ITEMS[**selected];
+
+    if action_state.just_pressed(Action::Up) {
+        **selected = (**selected + ITEMS.len() - 1) % ITEMS.len();
+    }
+    if action_state.just_pressed(Action::Down) {
+        **selected = (**selected + 1) % ITEMS.len();
+    }
+    if action_state.just_pressed(Action::Left) { item.adjust(&mut settings, -1.0) }
+    if action_state.just_pressed(Action::Right) { item.adjust(&mut settings, 1.0) }
+    if action_state.just_pressed(Action::Confirm) {
+        match item {
+            OptionItem::Calibrate => {
+                println!("options: moved state to Calibration from Options");
+                next_state.set(MenuState::Calibration);
+            }
+            OptionItem::Keybinds => {
+                println!("options: moved state to Keybinds from Options");
+                next_state.set(MenuState::Keybinds);
+            }
+            _ => {},
+        }
+    }
+    if action_state.just_pressed(Action::Back) {
+        // the main menu or the pause menu below comes back by itself
+        println!("options: moved state to Closed from Options");
+        next_state.set(MenuState::Closed);
+    }
+}
+
+fn update_text(
+    mut query: Query<(&mut Text, &OptionText)>,
+    settings: Res<Settings>,
+    selected: Res<Selected>,
+    gamepads: Res<Gamepads>,
+    locale: Locale,
+) {
+    if !settings.is_changed() && !selected.is_changed() && !gamepads.is_changed() && !locale.is_changed() { return }
+
+    let gamepad = gamepads.iter().next().is_some();
+    for (mut text, option) in query.iter_mut() {
+        let is_selected = ITEMS[**selected] == option.0;
+        let cursor = if is_selected { "> " } else { "  " };
+        text.sections[0].value = format!(
+            "{}{}: {}",
+            cursor,
+            locale.get(option.0.text_key()),
+            option.0.value(&locale, &settings, gamepad),
+        );
+        text.sections[0].style.color = if is_selected { SELECTED_COLOR } else { TEXT_COLOR };
+    }
+}
+
+pub struct OptionsPlugin;
+
+impl Plugin for OptionsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, open.run_if(
+                in_state(AppState::Mainmenu).and_then(in_state(MenuState::Closed))
+            ))
+            .add_systems(Update, open.run_if(
+                in_state(IngameState::Paused).and_then(in_state(MenuState::Closed))
+            ))
+            .add_systems(OnEnter(MenuState::Options), setup)
+            .add_systems(Update, (
+                update,
+                update_text,
+            ).chain().run_if(in_state(MenuState::Options)))
+        ;
+    }
+}
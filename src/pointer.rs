@@ -0,0 +1,62 @@
+use bevy::{
+    prelude::*,
+    ecs::system::SystemParam,
+    input::{
+        mouse::MouseButtonInput,
+        ButtonState,
+    },
+    window::PrimaryWindow,
+};
+
+use crate::viewport::{
+    viewport_to_world,
+    MainCamera,
+};
+
+// Mouse and touch positions in the world.
+// Anything off the view, outside the window or without a window is `None`.
+#[derive(SystemParam)]
+pub struct Pointer<'w, 's> {
+    window_query: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    camera_query: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<MainCamera>>,
+    mouse_button_events: EventReader<'w, 's, MouseButtonInput>,
+    mouse_input: Res<'w, ButtonInput<MouseButton>>,
+    touches: Res<'w, Touches>,
+}
+
+impl Pointer<'_, '_> {
+    pub fn to_world(&self, window_position: Vec2) -> Option<Vec2> {
+        let (camera, camera_transform) = self.camera_query.get_single().ok()?;
+        viewport_to_world(camera, camera_transform, window_position)
+    }
+
+    pub fn cursor(&self) -> Option<Vec2> {
+        let window = self.window_query.get_single().ok()?;
+        self.to_world(window.cursor_position()?)
+    }
+
+    // Where the left button or a finger went down this frame, and how many times.
+    // Reads the clicks, so call it every frame the system runs even if nothing is hit.
+    pub fn presses(&mut self) -> Vec<(Vec2, usize)> {
+        let clicks = self.mouse_button_events.read()
+            .filter(|event| event.button == MouseButton::Left && event.state == ButtonState::Pressed)
+            .count();
+        let cursor = self.cursor()
+            .filter(|_| clicks > 0)
+            .map(|pos| (pos, clicks));
+        let touches = self.touches.iter_just_pressed()
+            .filter_map(|touch| self.to_world(touch.position()))
+            .map(|pos| (pos, 1));
+
+        cursor.into_iter().chain(touches).collect()
+    }
+
+    // Where the left button or a finger is being held down
+    pub fn held(&self) -> Vec<Vec2> {
+        let cursor = self.cursor().filter(|_| self.mouse_input.pressed(MouseButton::Left));
+        let touches = self.touches.iter()
+            .filter_map(|touch| self.to_world(touch.position()));
+
+        cursor.into_iter().chain(touches).collect()
+    }
+}
@@ -0,0 +1,228 @@
+use bevy::prelude::*;
+
+use crate::{
+    AppState,
+    Score,
+};
+use crate::input::{
+    Action,
+    ActionState,
+};
+use crate::locale::{
+    HintKeys,
+    KeyHints,
+    LocalizedText,
+};
+use crate::menu::{
+    button_bundle,
+    MenuButton,
+    MenuPressed,
+};
+
+const TITLE_SIZE: f32 = 28.0;
+const BOARD_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const BOARD_PADDING: Val = Val::Px(20.0);
+const TEXT_SIZE: f32 = 16.0;
+const TEXT_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+const TEXT_PADDING: Val = Val::Px(8.0);
+
+// How a round ended, each shown on its own state with the same screen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Outcome {
+    Gameover,
+    Gameclear,
+}
+
+const OUTCOMES: [Outcome; 2] = [
+    Outcome::Gameover,
+    Outcome::Gameclear,
+];
+
+impl Outcome {
+    fn from_state(state: &AppState) -> Option<Self> {
+        OUTCOMES.into_iter().find(|outcome| outcome.state() == *state)
+    }
+
+    fn state(&self) -> AppState {
+        match self {
+            Outcome::Gameover => AppState::Gameover,
+            Outcome::Gameclear => AppState::Gameclear,
+        }
+    }
+
+    fn text_key(&self) -> &'static str {
+        match self {
+            Outcome::Gameover => "result.gameover",
+            Outcome::Gameclear => "result.gameclear",
+        }
+    }
+
+    fn shows_score(&self) -> bool {
+        *self == Outcome::Gameclear
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ResultItem {
+    Retry,
+    BackToTitle,
+}
+
+const ITEMS: [ResultItem; 2] = [
+    ResultItem::Retry,
+    ResultItem::BackToTitle,
+];
+
+impl ResultItem {
+    // the label names the keys of the action that also picks the item
+    fn hints(&self) -> &'static [(&'static str, HintKeys)] {
+        match self {
+            ResultItem::Retry => &[("result.retry", HintKeys::Action(Action::Retry))],
+            ResultItem::BackToTitle => &[("result.backtotitle", HintKeys::Action(Action::Back))],
+        }
+    }
+
+    fn next_state(&self) -> AppState {
+        match self {
+            ResultItem::Retry => AppState::Ingame,
+            ResultItem::BackToTitle => AppState::Mainmenu,
+        }
+    }
+}
+
+#[derive(Component)]
+struct ResultScreen;
+
+#[derive(Component)]
+struct ResultButton(ResultItem);
+
+fn setup(
+    mut commands: Commands,
+    state: Res<State<AppState>>,
+    score: Res<Score>,
+) {
+    let Some(outcome) = Outcome::from_state(state.get()) else { return };
+
+    println!("result: setup {:?}", outcome);
+    let text_style = TextStyle {
+        font_size: TEXT_SIZE,
+        color: TEXT_COLOR,
+        ..Default::default()
+    };
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ResultScreen,
+        StateScoped(outcome.state()),
+    ))
+    .insert(Name::new("result"))
+    .with_children(|parent| {
+        parent.spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(BOARD_PADDING),
+                ..Default::default()
+            },
+            background_color: BOARD_COLOR.into(),
+            ..Default::default()
+        })
+        .insert(Name::new("board"))
+        .with_children(|parent| {
+            // title
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: TITLE_SIZE,
+                        color: TEXT_COLOR,
+                        ..Default::default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(TEXT_PADDING),
+                    ..Default::default()
+                }),
+                LocalizedText(outcome.text_key()),
+            ))
+            .insert(Name::new("title"));
+            // score
+            if outcome.shows_score() {
+                parent.spawn((
+                    TextBundle::from_sections([
+                        TextSection::from_style(text_style.clone()),
+                        TextSection::new(score.to_string(), text_style.clone()),
+                    ])
+                    .with_style(Style {
+                        margin: UiRect::bottom(TEXT_PADDING),
+                        ..Default::default()
+                    }),
+                    LocalizedText("common.score"),
+                ))
+                .insert(Name::new("score"));
+            }
+            // buttons
+            for (index, item) in ITEMS.into_iter().enumerate() {
+                parent.spawn((
+                    button_bundle(),
+                    MenuButton(index),
+                    ResultButton(item),
+                ))
+                .insert(Name::new(format!("{:?}", item)))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section("", text_style.clone()),
+                        KeyHints(item.hints()),
+                    ));
+                });
+            }
+        });
+    });
+}
+
+fn update(
+    mut events: EventReader<MenuPressed>,
+    mut next_state: ResMut<NextState<AppState>>,
+    query: Query<&ResultButton>,
+    state: Res<State<AppState>>,
+    action_state: Res<ActionState>,
+) {
+    let mut pressed = events.read()
+        .filter_map(|event| query.get(event.0).ok())
+        .map(|button| button.0)
+        .last();
+    if action_state.just_pressed(Action::Retry) {
+        pressed = Some(ResultItem::Retry);
+    } else if action_state.just_pressed(Action::Back) {
+        pressed = Some(ResultItem::BackToTitle);
+    }
+
+    let Some(item) = pressed else { return };
+    let app_state = item.next_state();
+    println!("result: moved state to {:?} from {:?}", app_state, state.get());
+    next_state.set(app_state);
+}
+
+fn is_result(state: Res<State<AppState>>) -> bool {
+    Outcome::from_state(state.get()).is_some()
+}
+
+pub struct ResultPlugin;
+
+impl Plugin for ResultPlugin {
+    fn build(&self, app: &mut App) {
+        for outcome in OUTCOMES {
+            app.add_systems(OnEnter(outcome.state()), setup);
+        }
+        app.add_systems(Update, update.run_if(is_result));
+    }
+}
@@ -0,0 +1,261 @@
+use bevy::{
+    prelude::*,
+    window::{
+        PrimaryWindow,
+        WindowMode,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::PATH_SETTINGS;
+use crate::input::InputBindings;
+use crate::storage::{
+    load_ron,
+    save_ron,
+};
+
+pub const JUDGMENT_OFFSET_LIMIT: f32 = 0.3;
+pub const HIT_COOLDOWN_LIMIT: f32 = 0.5;
+pub const WINDOW_SCALE_RANGE: (f32, f32) = (1.0, 3.0);
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+pub const DIFFICULTIES: [Difficulty; 3] = [
+    Difficulty::Easy,
+    Difficulty::Normal,
+    Difficulty::Hard,
+];
+
+impl Difficulty {
+    // key of the name in the string tables
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "difficulty.easy",
+            Difficulty::Normal => "difficulty.normal",
+            Difficulty::Hard => "difficulty.hard",
+        }
+    }
+
+    pub fn speed_scale(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.75,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.25,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    pub fn prev(&self) -> Self {
+        self.next().next()
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    #[default]
+    Japanese,
+    English,
+}
+
+pub const LANGUAGES: [Language; 2] = [
+    Language::Japanese,
+    Language::English,
+];
+
+impl Language {
+    // each language is named in itself, so it can be found whatever is shown
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::Japanese => "日本語",
+            Language::English => "English",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Language::Japanese => Language::English,
+            Language::English => Language::Japanese,
+        }
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub master_volume: f32,
+    pub sfx_volume: f32,
+    pub music_volume: f32,
+    pub muted: bool,
+    // pause and silence the game while the window is in the background
+    pub auto_pause: bool,
+    // seconds the player tends to hit late (negative when early)
+    pub judgment_offset: f32,
+    // seconds after a hit in which further hits are ignored
+    pub hit_cooldown: f32,
+    // only the first hit while the cue crosses the bar once is judged
+    pub one_hit_per_pass: bool,
+    pub difficulty: Difficulty,
+    pub language: Language,
+    pub window_scale: f32,
+    pub fullscreen: bool,
+    // camera feedback during a round, for players who find it too much
+    pub screen_shake: bool,
+    pub hit_stop: bool,
+    pub zoom_pulse: bool,
+    pub bindings: InputBindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            sfx_volume: 1.0,
+            music_volume: 1.0,
+            muted: false,
+            auto_pause: true,
+            judgment_offset: 0.0,
+            hit_cooldown: 0.0,
+            one_hit_per_pass: false,
+            difficulty: Difficulty::default(),
+            language: Language::default(),
+            window_scale: 1.0,
+            fullscreen: false,
+            screen_shake: true,
+            hit_stop: true,
+            zoom_pulse: true,
+            bindings: InputBindings::default(),
+        }
+    }
+}
+
+impl Settings {
+    // a hand-edited file may hold anything, so every value is brought back in range
+    pub fn load() -> Self {
+        load_ron::<Self>(PATH_SETTINGS).clamped()
+    }
+
+    fn clamped(self) -> Self {
+        let default = Self::default();
+        // clamping keeps a NaN, so it falls back to the default instead
+        let limit = |value: f32, (min, max): (f32, f32), default: f32| {
+            if value.is_nan() { default } else { value.clamp(min, max) }
+        };
+
+        Self {
+            master_volume: limit(self.master_volume, (0.0, 1.0), default.master_volume),
+            sfx_volume: limit(self.sfx_volume, (0.0, 1.0), default.sfx_volume),
+            music_volume: limit(self.music_volume, (0.0, 1.0), default.music_volume),
+            judgment_offset: limit(
+                self.judgment_offset,
+                (-JUDGMENT_OFFSET_LIMIT, JUDGMENT_OFFSET_LIMIT),
+                default.judgment_offset,
+            ),
+            hit_cooldown: limit(self.hit_cooldown, (0.0, HIT_COOLDOWN_LIMIT), default.hit_cooldown),
+            window_scale: limit(self.window_scale, WINDOW_SCALE_RANGE, default.window_scale),
+            ..self
+        }
+    }
+
+    pub fn save(&self) {
+        save_ron(PATH_SETTINGS, self);
+    }
+}
+
+fn save(settings: Res<Settings>) {
+    if !settings.is_changed() || settings.is_added() { return }
+
+    println!("settings: save");
+    settings.save();
+}
+
+fn apply_window_scale(
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    settings: Res<Settings>,
+) {
+    if !settings.is_changed() { return }
+
+    let Ok(mut window) = window_query.get_single_mut() else { return };
+    if window.resolution.scale_factor_override() == Some(settings.window_scale) { return }
+
+    println!("settings: window scale {}", settings.window_scale);
+    window.resolution.set_scale_factor_override(Some(settings.window_scale));
+}
+
+fn apply_fullscreen(
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    settings: Res<Settings>,
+) {
+    if !settings.is_changed() { return }
+
+    let Ok(mut window) = window_query.get_single_mut() else { return };
+    let mode = if settings.fullscreen { WindowMode::BorderlessFullscreen } else { WindowMode::Windowed };
+    if window.mode == mode { return }
+
+    println!("settings: window mode {:?}", mode);
+    window.mode = mode;
+}
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, (
+                save,
+                apply_window_scale,
+                apply_fullscreen,
+            ))
+        ;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamped_keeps_values_in_range() {
+        let settings = Settings {
+            master_volume: 2.0,
+            sfx_volume: -1.0,
+            music_volume: 0.5,
+            judgment_offset: -1.0,
+            hit_cooldown: 10.0,
+            window_scale: 0.0,
+            ..Default::default()
+        }.clamped();
+        assert_eq!(settings.master_volume, 1.0);
+        assert_eq!(settings.sfx_volume, 0.0);
+        assert_eq!(settings.music_volume, 0.5);
+        assert_eq!(settings.judgment_offset, -JUDGMENT_OFFSET_LIMIT);
+        assert_eq!(settings.hit_cooldown, HIT_COOLDOWN_LIMIT);
+        assert_eq!(settings.window_scale, WINDOW_SCALE_RANGE.0);
+    }
+
+    #[test]
+    fn clamped_replaces_nan_with_the_default() {
+        let settings = Settings {
+            master_volume: f32::NAN,
+            window_scale: f32::NAN,
+            ..Default::default()
+        }.clamped();
+        assert_eq!(settings.master_volume, Settings::default().master_volume);
+        assert_eq!(settings.window_scale, Settings::default().window_scale);
+        // an infinite scale is still a number, and ends at the largest size
+        let settings = Settings { window_scale: f32::INFINITY, ..Default::default() }.clamped();
+        assert_eq!(settings.window_scale, WINDOW_SCALE_RANGE.1);
+    }
+}
@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+
+use crate::input::ActionSystem;
+use crate::pointer::Pointer;
+
+// Area around the button's position that answers the pointer
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HitShape {
+    Circle(f32),
+    // no button of the game is square enough for it yet
+    #[allow(dead_code)]
+    Rect(Vec2),
+}
+
+impl HitShape {
+    fn contains(&self, offset: Vec2) -> bool {
+        match self {
+            HitShape::Circle(radius) => offset.length() < *radius,
+            HitShape::Rect(size) => offset.abs().cmplt(*size / 2.0).all(),
+        }
+    }
+}
+
+// Atlas frame shown in each state; `None` leaves the frame to the animation
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ButtonFrames {
+    pub normal: Option<usize>,
+    pub hovered: Option<usize>,
+    pub pressed: Option<usize>,
+    pub disabled: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum ButtonState {
+    #[default]
+    Normal,
+    Hovered,
+    Pressed,
+    Disabled,
+}
+
+// A sprite that can be clicked or tapped
+#[derive(Component)]
+pub struct SpriteButton {
+    pub shape: HitShape,
+    pub frames: ButtonFrames,
+    pub disabled: bool,
+    state: ButtonState,
+}
+
+impl SpriteButton {
+    pub fn new(shape: HitShape) -> Self {
+        Self {
+            shape,
+            frames: ButtonFrames::default(),
+            disabled: false,
+            state: ButtonState::default(),
+        }
+    }
+
+    pub fn with_frames(mut self, frames: ButtonFrames) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    fn frame(&self) -> Option<usize> {
+        match self.state {
+            ButtonState::Normal => self.frames.normal,
+            ButtonState::Hovered => self.frames.hovered,
+            ButtonState::Pressed => self.frames.pressed,
+            ButtonState::Disabled => self.frames.disabled,
+        }
+    }
+}
+
+// Sent once a frame for a button that was hit, with how many clicks or taps hit it
+#[derive(Event)]
+pub struct ButtonPressed {
+    pub entity: Entity,
+    pub count: usize,
+}
+
+fn detect(
+    mut query: Query<(Entity, &mut SpriteButton, &GlobalTransform)>,
+    mut events: EventWriter<ButtonPressed>,
+    mut pointer: Pointer,
+) {
+    let presses = pointer.presses();
+    let held = pointer.held();
+    let cursor = pointer.cursor();
+
+    for (entity, mut button, transform) in query.iter_mut() {
+        let center = transform.translation().truncate();
+        let shape = button.shape;
+        let hits = |pos: &Vec2| shape.contains(*pos - center);
+
+        let state = if button.disabled {
+            ButtonState::Disabled
+        } else if held.iter().any(hits) {
+            ButtonState::Pressed
+        } else if cursor.as_ref().is_some_and(hits) {
+            ButtonState::Hovered
+        } else {
+            ButtonState::Normal
+        };
+        if button.state != state { button.state = state }
+        if button.disabled { continue }
+
+        let count = presses.iter()
+            .filter(|(pos, _)| hits(pos))
+            .map(|(_, count)| count)
+            .sum();
+        if count > 0 {
+            events.send(ButtonPressed { entity, count });
+        }
+    }
+}
+
+fn apply_frame(mut query: Query<(&SpriteButton, &mut TextureAtlas)>) {
+    for (button, mut atlas) in query.iter_mut() {
+        let Some(index) = button.frame() else { continue };
+        if atlas.index != index { atlas.index = index }
+    }
+}
+
+pub struct SpriteButtonPlugin;
+
+impl Plugin for SpriteButtonPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<ButtonPressed>()
+            // after input that a screen transition swallows is gone
+            .add_systems(PreUpdate, detect.after(ActionSystem))
+            // after the animations, which the frames of a state take over
+            .add_systems(PostUpdate, apply_frame)
+        ;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circle_contains_only_inside_its_radius() {
+        let shape = HitShape::Circle(10.0);
+        assert!(shape.contains(Vec2::ZERO));
+        assert!(shape.contains(Vec2::new(6.0, 6.0)));
+        assert!(!shape.contains(Vec2::new(8.0, 8.0)));
+        assert!(!shape.contains(Vec2::new(-10.0, 0.0)));
+    }
+
+    #[test]
+    fn rect_contains_its_corners() {
+        let shape = HitShape::Rect(Vec2::new(20.0, 10.0));
+        assert!(shape.contains(Vec2::new(9.0, 4.0)));
+        assert!(shape.contains(Vec2::new(-9.0, -4.0)));
+        assert!(!shape.contains(Vec2::new(11.0, 0.0)));
+        assert!(!shape.contains(Vec2::new(0.0, -6.0)));
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{
+    de::DeserializeOwned,
+    Serialize,
+};
+
+// Reads a ron file next to the game, or the default when it is missing or broken
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_ron<T: DeserializeOwned + Default>(path: &str) -> T {
+    let Ok(text) = std::fs::read_to_string(path) else {
+        println!("storage: {} not found, use default", path);
+        return T::default();
+    };
+    match ron::from_str(&text) {
+        Ok(value) => value,
+        Err(error) => {
+            println!("storage: failed to parse {}: {}", path, error);
+            T::default()
+        }
+    }
+}
+
+// the browser has no files to keep anything in
+#[cfg(target_arch = "wasm32")]
+pub fn load_ron<T: DeserializeOwned + Default>(_path: &str) -> T {
+    T::default()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_ron<T: Serialize>(path: &str, value: &T) {
+    let config = ron::ser::PrettyConfig::default();
+    let text = match ron::ser::to_string_pretty(value, config) {
+        Ok(text) => text,
+        Err(error) => {
+            println!("storage: failed to serialize {}: {}", path, error);
+            return;
+        }
+    };
+    if let Err(error) = std::fs::write(path, text) {
+        println!("storage: failed to write {}: {}", path, error);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save_ron<T: Serialize>(_path: &str, _value: &T) {}
@@ -0,0 +1,228 @@
+use bevy::{
+    prelude::*,
+    input::{
+        mouse::MouseButtonInput,
+        InputSystem,
+    },
+    ui::{
+        FocusPolicy,
+        UiSystem,
+    },
+    utils::HashMap,
+};
+
+use crate::AppState;
+use crate::input::ActionSystem;
+
+const CURTAIN_COLOR: Color = Color::BLACK;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransitionStyle {
+    Fade,
+    // a curtain sweeps in from the left and leaves to the right
+    Wipe,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transition {
+    pub style: TransitionStyle,
+    // seconds for each half, covering and uncovering the screen
+    pub secs: f32,
+}
+
+impl Transition {
+    pub const fn new(style: TransitionStyle, secs: f32) -> Self {
+        Self { style, secs }
+    }
+}
+
+// How each change of `AppState` is animated, falling back to `default`
+#[derive(Resource)]
+pub struct Transitions {
+    pub default: Transition,
+    pub rules: HashMap<(AppState, AppState), Transition>,
+}
+
+impl Default for Transitions {
+    fn default() -> Self {
+        let wipe = Transition::new(TransitionStyle::Wipe, 0.4);
+        let fade = Transition::new(TransitionStyle::Fade, 0.5);
+
+        Self {
+            default: Transition::new(TransitionStyle::Fade, 0.3),
+            rules: HashMap::from([
+                ((AppState::Mainmenu, AppState::Ingame), wipe),
+                ((AppState::Gameover, AppState::Ingame), wipe),
+                ((AppState::Gameclear, AppState::Ingame), wipe),
+                ((AppState::Ingame, AppState::Restart), wipe),
+                ((AppState::Ingame, AppState::Gameover), fade),
+                ((AppState::Ingame, AppState::Gameclear), fade),
+            ]),
+        }
+    }
+}
+
+impl Transitions {
+    pub fn get(&self, from: &AppState, to: &AppState) -> Transition {
+        self.rules.get(&(from.clone(), to.clone())).copied().unwrap_or(self.default)
+    }
+}
+
+#[derive(Default)]
+enum Phase {
+    #[default]
+    Idle,
+    // covering the screen, the state changes once it is hidden
+    Out(AppState),
+    In,
+}
+
+#[derive(Resource, Default)]
+struct ScreenTransition {
+    phase: Phase,
+    transition: Option<Transition>,
+    timer: Timer,
+}
+
+#[derive(Component)]
+struct Curtain;
+
+fn setup(mut commands: Commands) {
+    println!("transition: setup");
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..Default::default()
+            },
+            background_color: CURTAIN_COLOR.with_alpha(0.0).into(),
+            // the curtain also keeps clicks away from the buttons below
+            focus_policy: FocusPolicy::Block,
+            visibility: Visibility::Hidden,
+            z_index: ZIndex::Global(i32::MAX),
+            ..Default::default()
+        },
+        Curtain,
+    ))
+    .insert(Name::new("curtain"));
+}
+
+// Holds back state changes until the screen is covered
+fn intercept(
+    mut screen_transition: ResMut<ScreenTransition>,
+    mut next_state: ResMut<NextState<AppState>>,
+    transitions: Res<Transitions>,
+    state: Res<State<AppState>>,
+) {
+    let NextState::Pending(target) = next_state.as_ref() else { return };
+    let target = target.clone();
+
+    match screen_transition.phase {
+        Phase::Idle => {},
+        // the curtain has just lifted this state change itself
+        Phase::In => return,
+        Phase::Out(_) => {
+            println!("transition: dropped {:?} while moving", target);
+            next_state.reset();
+            return;
+        }
+    }
+    if target == *state.get() { return }
+
+    let transition = transitions.get(state.get(), &target);
+
+    println!("transition: {:?} from {:?} to {:?}", transition.style, state.get(), target);
+    next_state.reset();
+    screen_transition.timer = Timer::from_seconds(transition.secs, TimerMode::Once);
+    screen_transition.transition = Some(transition);
+    screen_transition.phase = Phase::Out(target);
+}
+
+fn update(
+    mut screen_transition: ResMut<ScreenTransition>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut query: Query<(&mut Style, &mut BackgroundColor, &mut Visibility), With<Curtain>>,
+    time: Res<Time>,
+) {
+    let Some(transition) = screen_transition.transition else { return };
+    let Ok((mut style, mut color, mut visibility)) = query.get_single_mut() else { return };
+
+    let finished = screen_transition.timer.tick(time.delta()).finished();
+    let progress = screen_transition.timer.fraction();
+    // how much of the screen is covered, and where the curtain starts
+    let (cover, left) = match screen_transition.phase {
+        Phase::Idle => return,
+        Phase::Out(_) => (progress, 0.0),
+        Phase::In => (1.0 - progress, progress),
+    };
+
+    *visibility = Visibility::Visible;
+    match transition.style {
+        TransitionStyle::Fade => {
+            style.left = Val::Percent(0.0);
+            style.width = Val::Percent(100.0);
+            *color = CURTAIN_COLOR.with_alpha(cover).into();
+        }
+        TransitionStyle::Wipe => {
+            style.left = Val::Percent(left * 100.0);
+            style.width = Val::Percent(cover * 100.0);
+            *color = CURTAIN_COLOR.into();
+        }
+    }
+    if !finished { return }
+
+    match std::mem::take(&mut screen_transition.phase) {
+        Phase::Out(target) => {
+            println!("transition: moved state to {:?}", target);
+            next_state.set(target);
+            screen_transition.timer.reset();
+            screen_transition.phase = Phase::In;
+        }
+        _ => {
+            screen_transition.transition = None;
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
+
+// Input is swallowed before actions and UI buttons can see it
+fn block_input(
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    mut mouse_input: ResMut<ButtonInput<MouseButton>>,
+    mut gamepad_buttons: ResMut<ButtonInput<GamepadButton>>,
+    mut touches: ResMut<Touches>,
+    mut mouse_button_events: ResMut<Events<MouseButtonInput>>,
+) {
+    keyboard_input.clear();
+    mouse_input.clear();
+    gamepad_buttons.clear();
+    touches.clear();
+    mouse_button_events.clear();
+}
+
+fn is_running(screen_transition: Res<ScreenTransition>) -> bool {
+    !matches!(screen_transition.phase, Phase::Idle)
+}
+
+pub struct TransitionPlugin;
+
+impl Plugin for TransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Transitions>()
+            .init_resource::<ScreenTransition>()
+            .add_systems(Startup, setup)
+            .add_systems(PreUpdate, block_input
+                .run_if(is_running)
+                .after(InputSystem)
+                .before(ActionSystem)
+                .before(UiSystem::Focus)
+            )
+            .add_systems(Update, update)
+            // runs last so that every system of the frame had its chance to change state
+            .add_systems(Last, intercept)
+        ;
+    }
+}
@@ -0,0 +1,124 @@
+use bevy::{
+    prelude::*,
+    render::{
+        camera::{
+            ScalingMode,
+            Viewport,
+        },
+        view::RenderLayers,
+    },
+    ui::UiSystem,
+    window::PrimaryWindow,
+};
+
+use crate::WINDOW_SIZE;
+use crate::input::{
+    Action,
+    ActionState,
+};
+use crate::settings::Settings;
+
+const LETTERBOX_COLOR: Color = Color::BLACK;
+// nothing is drawn on this layer, so its camera only clears the bars
+const LETTERBOX_LAYER: usize = 1;
+
+// The camera showing the game, scaled to fit `WINDOW_SIZE` into any window
+#[derive(Component)]
+pub struct MainCamera;
+
+// Position in the world under a point of the window, or `None` over the bars
+pub fn viewport_to_world(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    window_position: Vec2,
+) -> Option<Vec2> {
+    let viewport = camera.logical_viewport_rect()?;
+    if !viewport.contains(window_position) { return None }
+
+    camera.viewport_to_world_2d(camera_transform, window_position - viewport.min)
+}
+
+fn setup(mut commands: Commands) {
+    println!("viewport: setup");
+    commands.spawn((
+        Camera2dBundle {
+            projection: OrthographicProjection {
+                scaling_mode: ScalingMode::Fixed { width: WINDOW_SIZE.x, height: WINDOW_SIZE.y },
+                ..Camera2dBundle::default().projection
+            },
+            ..Default::default()
+        },
+        MainCamera,
+        IsDefaultUiCamera,
+    ))
+    .insert(Name::new("camera"));
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                order: -1,
+                clear_color: ClearColorConfig::Custom(LETTERBOX_COLOR),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        RenderLayers::layer(LETTERBOX_LAYER),
+    ))
+    .insert(Name::new("letterbox"));
+}
+
+// Keeps the aspect ratio of `WINDOW_SIZE` in the middle of the window
+fn fit(
+    mut camera_query: Query<&mut Camera, With<MainCamera>>,
+    mut ui_scale: ResMut<UiScale>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+) {
+    let Ok(window) = window_query.get_single() else { return };
+    let Ok(mut camera) = camera_query.get_single_mut() else { return };
+
+    let window_size = window.physical_size().as_vec2();
+    // physical pixels per pixel of the virtual screen
+    let scale = (window_size / WINDOW_SIZE).min_element();
+    if scale <= 0.0 { return }
+
+    let size = WINDOW_SIZE * scale;
+    let viewport = Viewport {
+        physical_position: ((window_size - size) / 2.0).as_uvec2(),
+        physical_size: size.as_uvec2().max(UVec2::ONE),
+        ..Default::default()
+    };
+    let is_same = camera.viewport.as_ref().is_some_and(|current| {
+        current.physical_position == viewport.physical_position
+            && current.physical_size == viewport.physical_size
+    });
+    if !is_same {
+        println!("viewport: {:?} at {:?}", viewport.physical_size, viewport.physical_position);
+        camera.viewport = Some(viewport);
+    }
+
+    // the UI is laid out on the virtual screen too
+    let ui = scale / window.scale_factor();
+    if ui_scale.0 != ui { ui_scale.0 = ui }
+}
+
+fn toggle_fullscreen(
+    mut settings: ResMut<Settings>,
+    action_state: Res<ActionState>,
+) {
+    if !action_state.just_pressed(Action::Fullscreen) { return }
+
+    settings.fullscreen = !settings.fullscreen;
+    println!("viewport: fullscreen {}", settings.fullscreen);
+}
+
+pub struct ViewportPlugin;
+
+impl Plugin for ViewportPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Startup, setup)
+            .add_systems(Update, toggle_fullscreen)
+            // before anything is laid out or drawn with the previous size
+            .add_systems(PostUpdate, fit.before(UiSystem::Layout))
+        ;
+    }
+}